@@ -0,0 +1,108 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Mul};
+
+const BASE: u64 = 1_000_000_000;
+
+/// Arbitrary-precision unsigned integer, stored as base 10^9 digits with the least significant
+/// first and no leading zero digits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BigUint {
+    digits: Vec<u32>,
+}
+
+impl BigUint {
+    fn from_digits(mut digits: Vec<u64>) -> BigUint {
+        let mut carry = 0;
+        for digit in digits.iter_mut() {
+            *digit += carry;
+            carry = *digit / BASE;
+            *digit %= BASE;
+        }
+        while carry > 0 {
+            digits.push(carry % BASE);
+            carry /= BASE;
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        Self {
+            digits: digits.into_iter().map(|digit| digit as u32).collect(),
+        }
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_digits(vec![value])
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.digits.len().max(other.digits.len());
+        let digit = |digits: &[u32], i: usize| digits.get(i).copied().unwrap_or(0) as u64;
+
+        BigUint::from_digits(
+            (0..len)
+                .map(|i| digit(&self.digits, i) + digit(&other.digits, i))
+                .collect(),
+        )
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut digits = vec![0u64; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let value = digits[i + j] + a as u64 * b as u64 + carry;
+                digits[i + j] = value % BASE;
+                carry = value / BASE;
+            }
+            digits[i + other.digits.len()] += carry;
+        }
+
+        BigUint::from_digits(digits)
+    }
+}
+
+impl Display for BigUint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.digits.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter()
+                    .rev()
+                    .try_for_each(|digit| write!(f, "{:09}", digit))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bigint::BigUint;
+
+    #[test]
+    fn arithmetic() {
+        let value = BigUint::from(u64::MAX);
+        let one = BigUint::from(1);
+
+        assert_eq!((&value + &one).to_string(), "18446744073709551616");
+        assert_eq!(
+            (&(&value + &one) * &(&value + &one)).to_string(),
+            "340282366920938463463374607431768211456"
+        );
+        assert_eq!(
+            (&BigUint::from(1_000_000_000) * &BigUint::default()).to_string(),
+            "0"
+        );
+    }
+}
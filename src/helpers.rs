@@ -1,58 +1,65 @@
 use std::str::FromStr;
 
-pub fn parse_lines<T: FromStr>(input: Option<String>) -> Vec<T> {
-    input
-        .unwrap_or_default()
-        .lines()
-        .filter_map(|line| line.parse().ok())
-        .collect()
+#[allow(dead_code)]
+pub fn parse_lines<T: FromStr>(input: Option<&str>) -> Vec<T> {
+    parse_lines_iter(input).collect()
 }
 
-pub trait VecAsMatrix<T> {
-    fn neighbour_indexes(&self, index: usize, width: usize) -> Vec<usize>;
-    fn neighbours(&self, index: usize, width: usize) -> Vec<&T>;
-    fn at_position(&self, x: i32, y: i32, width: usize) -> Option<&T>;
+/// Lazy counterpart of `parse_lines`, lines that fail to parse are skipped
+pub fn parse_lines_iter<T: FromStr>(input: Option<&str>) -> impl Iterator<Item = T> + '_ {
+    lines(input).filter_map(|line| line.parse().ok())
 }
 
-impl<T> VecAsMatrix<T> for [T] {
-    fn neighbour_indexes(&self, index: usize, width: usize) -> Vec<usize> {
-        let x = index % width;
-        let y = index / width;
+/// Groups of consecutive non-empty lines along with the 1-based number of their first line
+pub fn blocks(input: &str) -> Vec<(usize, Vec<&str>)> {
+    let mut result: Vec<(usize, Vec<&str>)> = vec![];
+    let mut previous_empty = true;
 
-        let mut result = vec![];
-        if x > 0 {
-            result.push(x - 1 + y * width);
+    for (i, line) in input.lines().enumerate() {
+        match (line.is_empty(), previous_empty) {
+            (true, _) => {}
+            (false, true) => result.push((i + 1, vec![line])),
+            (false, false) => result.last_mut().unwrap().1.push(line),
         }
+        previous_empty = line.is_empty();
+    }
 
-        if y > 0 {
-            result.push(x + (y - 1) * width);
-        }
+    result
+}
 
-        if (x + 1) < width {
-            result.push(x + 1 + y * width);
-        }
+/// Lines borrowed from the input
+pub fn lines(input: Option<&str>) -> impl Iterator<Item = &str> {
+    input.unwrap_or_default().lines()
+}
 
-        if (x + (y + 1) * width) < self.len() {
-            result.push(x + (y + 1) * width);
-        }
+/// SplitMix64 generator, small and seedable so that simulations are reproducible
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
 
-        result
+#[allow(dead_code)]
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Self(seed)
     }
 
-    fn neighbours(&self, index: usize, width: usize) -> Vec<&T> {
-        self.neighbour_indexes(index, width)
-            .iter()
-            .map(|&index| &self[index])
-            .collect()
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
     }
 
-    fn at_position(&self, x: i32, y: i32, width: usize) -> Option<&T> {
-        let index = x + y * width as i32;
+    /// Number in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
 
-        if index < 0 || index >= self.len() as i32 {
-            return None;
+    /// Fisher-Yates shuffle
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
         }
-
-        Some(&self[index as usize])
     }
 }
@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+const BOM: char = '\u{feff}';
+
+/// Changes made by `normalize`, line numbers are 1-based
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Normalization {
+    pub bom: bool,
+    pub crlf_lines: Vec<usize>,
+    pub trailing_whitespace_lines: Vec<usize>,
+    pub final_newline: bool,
+}
+
+impl Normalization {
+    pub fn is_empty(&self) -> bool {
+        *self == Normalization::default()
+    }
+}
+
+/// Joins sorted line numbers into ranges, e.g. `1-3, 7`
+fn line_ranges(lines: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for &line in lines {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == line => *end = line,
+            _ => ranges.push((line, line)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for Normalization {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "input is already normalized");
+        }
+
+        if self.bom {
+            writeln!(f, "removed the byte order mark")?;
+        }
+        if !self.crlf_lines.is_empty() {
+            writeln!(
+                f,
+                "converted CRLF line endings on lines {}",
+                line_ranges(&self.crlf_lines)
+            )?;
+        }
+        if !self.trailing_whitespace_lines.is_empty() {
+            writeln!(
+                f,
+                "removed trailing whitespace on lines {}",
+                line_ranges(&self.trailing_whitespace_lines)
+            )?;
+        }
+        if self.final_newline {
+            writeln!(f, "added the missing final newline")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a line into its content without the line ending nor trailing whitespace,
+/// whether it ended with CRLF and whether trailing whitespace was dropped
+fn split_line(line: &str) -> (&str, bool, bool) {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let (line, crlf) = match line.strip_suffix('\r') {
+        Some(line) => (line, true),
+        None => (line, false),
+    };
+    let content = line.trim_end_matches([' ', '\t']);
+
+    (content, crlf, content.len() != line.len())
+}
+
+/// Removes the byte order mark, converts CRLF line endings, strips trailing whitespace
+/// and adds the final newline when it's missing. The input is borrowed when nothing changes.
+pub fn normalize(input: &str) -> (Cow<'_, str>, Normalization) {
+    let (body, bom) = match input.strip_prefix(BOM) {
+        Some(body) => (body, true),
+        None => (input, false),
+    };
+
+    let mut report = Normalization {
+        bom,
+        final_newline: !body.is_empty() && !body.ends_with('\n'),
+        ..Default::default()
+    };
+    for (i, line) in body.split_inclusive('\n').enumerate() {
+        let (_, crlf, trailing) = split_line(line);
+        if crlf {
+            report.crlf_lines.push(i + 1);
+        }
+        if trailing {
+            report.trailing_whitespace_lines.push(i + 1);
+        }
+    }
+
+    if report.is_empty() {
+        return (Cow::Borrowed(input), report);
+    }
+
+    let mut result = String::with_capacity(body.len() + 1);
+    for line in body.split_inclusive('\n') {
+        result.push_str(split_line(line).0);
+        result.push('\n');
+    }
+
+    (Cow::Owned(result), report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::input::{normalize, Normalization};
+
+    #[test]
+    fn normalize_input() {
+        let (input, report) = normalize("\u{feff}#.# \r\n..#\r\n  #\t\n\n#..");
+        assert_eq!(input, "#.#\n..#\n  #\n\n#..\n");
+        assert_eq!(
+            report,
+            Normalization {
+                bom: true,
+                crlf_lines: vec![1, 2],
+                trailing_whitespace_lines: vec![1, 3],
+                final_newline: true,
+            }
+        );
+        assert_eq!(
+            report.to_string(),
+            "removed the byte order mark\n\
+             converted CRLF line endings on lines 1-2\n\
+             removed trailing whitespace on lines 1, 3\n\
+             added the missing final newline\n"
+        );
+    }
+
+    #[test]
+    fn normalize_unchanged_input() {
+        let (input, report) = normalize("3,4,3,1,2\n");
+        assert!(matches!(input, Cow::Borrowed("3,4,3,1,2\n")));
+        assert!(report.is_empty());
+
+        let (input, report) = normalize("");
+        assert_eq!(input, "");
+        assert!(report.is_empty());
+    }
+}
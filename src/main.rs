@@ -1,10 +1,14 @@
-#![feature(pattern)]
+use std::fs::File;
+use std::io::{BufRead, BufReader};
 
 use clap::{App, AppSettings, Arg, SubCommand};
 
+use matrix::Matrix;
 use solutions::*;
 
+mod bigint;
 mod helpers;
+mod input;
 mod matrix;
 mod solutions;
 
@@ -12,6 +16,22 @@ macro_rules! solutions {
     ($( $mod_name:ident ), *) => { vec![$( Box::new($mod_name::DaySolution::default()),)*] };
 }
 
+fn positive(value: String) -> core::result::Result<(), String> {
+    match value.parse::<usize>().map_err(|e| e.to_string())? {
+        0 => Err("must be greater than 0".into()),
+        _ => Ok(()),
+    }
+}
+
+fn target(value: &str) -> core::result::Result<(i64, i64), String> {
+    let (horizontal, depth) = value.split_once(',').ok_or("expected `horizontal,depth`")?;
+
+    Ok((
+        horizontal.trim().parse().map_err(|e| format!("{}", e))?,
+        depth.trim().parse().map_err(|e| format!("{}", e))?,
+    ))
+}
+
 fn main() -> Result<()> {
     let mut solutions: Vec<Box<dyn Solution>> = solutions!(
         day1, day2, day3, day4, day5, day6, day7, day8, day9, day10, day11, day12, day13, day14,
@@ -48,9 +68,175 @@ fn main() -> Result<()> {
                 .help("Input value")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("image")
+                .short("i")
+                .long("image")
+                .help("Export the final grid to an image file (.pbm, .pgm, .ppm or .svg)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("scale")
+                .long("scale")
+                .help("Image pixels per grid cell")
+                .default_value("1")
+                .validator(positive)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("dump")
+                .long("dump")
+                .help("Save the grid after --steps steps to a snapshot file (.txt or .bin)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("steps")
+                .long("steps")
+                .help("Simulation steps before the grid is saved with --dump")
+                .default_value("0")
+                .validator(|v| v.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("load")
+                .long("load")
+                .help("Resume from a grid snapshot file (.txt or .bin) saved with --dump")
+                .conflicts_with_all(&["file", "value"])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("trace")
+                .long("trace")
+                .help("Export the per-step state of the simulation to a file (.csv or .json)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("ties")
+                .long("ties")
+                .help("Bits kept on ties by gamma, oxygen and CO2, 010 by default (day 3)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("threshold")
+                .long("threshold")
+                .help("Segments that have to overlap at a point (day 5)")
+                .validator(positive)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("cycle")
+                .long("cycle")
+                .help("Days between two spawns of a fish (day 6) [default: 7]")
+                .validator(positive)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("newborn-delay")
+                .long("newborn-delay")
+                .help("Extra days before a newborn fish first spawns (day 6) [default: 2]")
+                .validator(|v| v.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("death-age")
+                .long("death-age")
+                .help("Age in days at which fish die, they never do by default (day 6)")
+                .validator(positive)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("days")
+                .long("days")
+                .help("Days simulated by both parts instead of the puzzle's (day 6)")
+                .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("modulus")
+                .long("modulus")
+                .help("Count the population modulo this number (day 6)")
+                .validator(positive)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("exact")
+                .long("exact")
+                .help("Count the population with arbitrary precision (day 6)")
+                .conflicts_with("modulus"),
+        )
+        .arg(
+            Arg::with_name("raw")
+                .long("raw")
+                .help("Pass the input to the solution as is, without normalizing it"),
+        )
         .subcommand(SubCommand::with_name("part1").about("Get 1st solution"))
         .subcommand(SubCommand::with_name("part2").about("Get 2nd solution"))
         .subcommand(SubCommand::with_name("all").about("Get all solutions"))
+        .subcommand(
+            SubCommand::with_name("lint")
+                .about("Run the day's parser on the input without solving"),
+        )
+        .subcommand(
+            SubCommand::with_name("analyze")
+                .about("Print a detailed report on the input, streamed from --file")
+                .arg(
+                    Arg::with_name("window")
+                        .long("window")
+                        .help("Measurements summed in each window")
+                        .default_value("1")
+                        .validator(positive)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("jumps")
+                        .long("jumps")
+                        .help("Largest jumps between measurements to list")
+                        .default_value("3")
+                        .validator(|v| v.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("trials")
+                        .long("trials")
+                        .help("Games played with the drawn numbers shuffled")
+                        .default_value("10000")
+                        .validator(positive)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("seed")
+                        .long("seed")
+                        .help("Seed of the shuffled draws")
+                        .default_value("0")
+                        .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+                        .takes_value(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("plan")
+                .about("Print the shortest input reaching --target")
+                .arg(
+                    Arg::with_name("target")
+                        .long("target")
+                        .help("Horizontal position and depth to reach, as `horizontal,depth`")
+                        .required(true)
+                        .validator(|v| target(&v).map(|_| ()))
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("max")
+                        .long("max")
+                        .help("Largest value of a single command")
+                        .default_value("9")
+                        .validator(positive)
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("simple")
+                        .long("simple")
+                        .help("Plan for the part 1 movement model instead of the part 2 one"),
+                ),
+        )
         .settings(&[AppSettings::SubcommandRequired])
         .get_matches();
 
@@ -59,22 +245,133 @@ fn main() -> Result<()> {
     let file = matches.value_of("file");
     let value = matches.value_of("value");
 
-    let input_value = match (file, value) {
-        (Some(file), _) => std::fs::read_to_string(file).ok(),
-        (_, Some(value)) => Some(value.to_string()),
-        (_, _) => None,
+    let mut options = Options {
+        ties: matches.value_of("ties").map(str::to_string),
+        threshold: matches.value_of("threshold").map(str::parse).transpose()?,
+        cycle: matches.value_of("cycle").map(str::parse).transpose()?,
+        newborn_delay: matches
+            .value_of("newborn-delay")
+            .map(str::parse)
+            .transpose()?,
+        death_age: matches.value_of("death-age").map(str::parse).transpose()?,
+        days: matches.value_of("days").map(str::parse).transpose()?,
+        modulus: matches.value_of("modulus").map(str::parse).transpose()?,
+        exact: matches.is_present("exact"),
+        ..Options::default()
     };
 
-    let mut results: Vec<String> = vec![];
+    if let Some(analyze) = matches.subcommand_matches("analyze") {
+        options.window = Some(analyze.value_of("window").unwrap().parse()?);
+        options.jumps = Some(analyze.value_of("jumps").unwrap().parse()?);
+        options.trials = Some(analyze.value_of("trials").unwrap().parse()?);
+        options.seed = Some(analyze.value_of("seed").unwrap().parse()?);
+        solution.configure(&options)?;
+
+        let mut reader: Box<dyn BufRead> = match (file, value) {
+            (Some(file), _) => Box::new(BufReader::new(File::open(file)?)),
+            (_, Some(value)) => Box::new(value.as_bytes()),
+            (_, _) => return Err("no input to analyze".into()),
+        };
+
+        return match solution.analyze(&mut reader)? {
+            Some(report) => {
+                print!("{}", report);
+                Ok(())
+            }
+            None => Err(format!("day {} has no analysis", day).into()),
+        };
+    }
+
+    if let Some(plan) = matches.subcommand_matches("plan") {
+        options.target = Some(target(plan.value_of("target").unwrap())?);
+        options.max_value = Some(plan.value_of("max").unwrap().parse()?);
+        options.simple = plan.is_present("simple");
+        solution.configure(&options)?;
+
+        return match solution.plan()? {
+            Some(course) => {
+                print!("{}", course);
+                Ok(())
+            }
+            None => Err(format!("day {} has nothing to plan", day).into()),
+        };
+    }
+
+    let input_value = match (file, value, matches.value_of("load")) {
+        (Some(file), _, _) => std::fs::read_to_string(file).ok(),
+        (_, Some(value), _) => Some(value.to_string()),
+        (_, _, Some(snapshot)) => {
+            let (grid, step) = Matrix::<char>::load_snapshot(snapshot)?;
+            options.start_step = step;
+            Some(grid.to_string())
+        }
+        (_, _, _) => None,
+    };
+    let normalized = input_value.as_deref().map(input::normalize);
+    let input = match (&normalized, matches.is_present("raw")) {
+        (Some((normalized, _)), false) => Some(normalized.as_ref()),
+        (_, _) => input_value.as_deref(),
+    };
+
+    solution.configure(&options)?;
 
     let subcommand = matches.subcommand_name().unwrap_or_default();
+    if subcommand == "lint" {
+        match &normalized {
+            Some((_, report)) => print!("{}", report),
+            None => return Err("no input to lint".into()),
+        }
+
+        let problems = solution.lint(input.unwrap());
+        problems.iter().for_each(|problem| println!("{}", problem));
+
+        return match problems.len() {
+            0 => {
+                println!("no problems found");
+                Ok(())
+            }
+            1 => Err("1 problem found".into()),
+            count => Err(format!("{} problems found", count).into()),
+        };
+    }
+
+    if let Some(path) = matches.value_of("dump") {
+        let steps = matches.value_of("steps").unwrap().parse::<usize>()?;
+
+        match solution.snapshot(input, steps)? {
+            Some(grid) => grid.save_snapshot(path, options.start_step + steps)?,
+            None => return Err(format!("day {} has no grid to save", day).into()),
+        }
+    }
+
+    if let Some(path) = matches.value_of("image") {
+        let scale = matches.value_of("scale").unwrap().parse::<usize>()?;
+
+        match solution.render(input)? {
+            Some(image) => image.save_image(path, scale)?,
+            None => return Err(format!("day {} has no grid to export", day).into()),
+        }
+    }
+
+    if let Some(path) = matches.value_of("trace") {
+        let format = TraceFormat::from_path(path.as_ref())
+            .ok_or_else(|| format!("unsupported trace format: {}", path))?;
+
+        match solution.trace(input, format)? {
+            Some(trace) => std::fs::write(path, trace)?,
+            None => return Err(format!("day {} has no trace to export", day).into()),
+        }
+    }
+
+    let mut results: Vec<String> = vec![];
+
     if subcommand == "all" || subcommand == "part1" {
-        let result = solution.part_1(input_value.clone())?;
+        let result = solution.part_1(input)?;
         results.push(format!("Part #1: {}", result));
     }
 
     if subcommand == "all" || subcommand == "part2" {
-        let result = solution.part_2(input_value)?;
+        let result = solution.part_2(input)?;
         results.push(format!("Part #2: {}", result));
     }
 
@@ -0,0 +1,321 @@
+use std::mem;
+
+use crate::matrix::parse::MatrixError;
+use crate::matrix::{parallel, Matrix, MATRIX_NEIGHBOURS_4, MATRIX_NEIGHBOURS_8};
+
+/// What the cells outside of the grid look like to the rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum Border<T> {
+    /// Cells outside of the grid always read as the given value
+    Fixed(T),
+    /// The grid wraps around on both axes
+    Wrap,
+    /// The grid is surrounded by an infinite background which evolves with the rules,
+    /// the grid grows by one cell on every side each step
+    Infinite(T),
+}
+
+/// Computes the next value of a cell from its neighbourhood
+pub type Rule<T> = dyn Fn(&Neighbourhood<T>) -> T + Sync;
+
+/// Read-only view of a cell and its surroundings, handed over to the rules
+pub struct Neighbourhood<'a, T> {
+    grid: &'a Matrix<T>,
+    border: &'a Border<T>,
+    x: i32,
+    y: i32,
+}
+
+impl<'a, T> Neighbourhood<'a, T> {
+    /// Neighbourhood of the cell at `index` of the row-major data of `grid`
+    pub(crate) fn at(grid: &'a Matrix<T>, border: &'a Border<T>, index: usize) -> Self {
+        Self {
+            grid,
+            border,
+            x: (index % grid.width) as i32,
+            y: (index / grid.width) as i32,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn cell(&self) -> &'a T {
+        &self.grid.data[self.x as usize + self.y as usize * self.grid.width]
+    }
+
+    #[allow(dead_code)]
+    pub fn position(&self) -> (usize, usize) {
+        (self.x as usize, self.y as usize)
+    }
+
+    /// Cell at the offset from the current one, the border applies outside of the grid
+    #[allow(dead_code)]
+    pub fn get(&self, dx: i32, dy: i32) -> &'a T {
+        let (x, y) = (self.x + dx, self.y + dy);
+
+        match self.border {
+            Border::Fixed(value) | Border::Infinite(value) => self.grid.get(x, y).unwrap_or(value),
+            Border::Wrap => self.grid.get_wrapped(x, y).unwrap(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours<'b>(&'b self, offsets: &'b [(i32, i32)]) -> impl Iterator<Item = &'a T> + 'b {
+        offsets.iter().map(move |&(dx, dy)| self.get(dx, dy))
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours4(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.neighbours(&MATRIX_NEIGHBOURS_4)
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours8(&self) -> impl Iterator<Item = &'a T> + '_ {
+        self.neighbours(&MATRIX_NEIGHBOURS_8)
+    }
+
+    /// The 3×3 window centered on the cell, row by row from the top left corner
+    #[allow(dead_code)]
+    pub fn window3(&self) -> impl Iterator<Item = &'a T> + '_ {
+        (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| self.get(dx, dy)))
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Maps every cell from its neighbourhood, the border applies outside of the grid
+    #[allow(dead_code)]
+    pub fn map_neighbourhood<U, F>(&self, border: &Border<T>, func: F) -> Matrix<U>
+    where
+        F: Fn(&Neighbourhood<T>) -> U,
+    {
+        Matrix {
+            width: self.width,
+            height: self.height,
+            data: (0..self.size())
+                .map(|index| func(&Neighbourhood::at(self, border, index)))
+                .collect(),
+        }
+    }
+}
+
+/// Synchronous cellular automaton over a matrix, every cell of the next generation is
+/// computed from the previous one only
+pub struct Automaton<T> {
+    pub grid: Matrix<T>,
+    pub border: Border<T>,
+    pub generation: usize,
+    threads: usize,
+    buffer: Vec<T>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync> Automaton<T> {
+    /// A wrapping grid has to have cells to wrap around to
+    #[allow(dead_code)]
+    pub fn new(grid: Matrix<T>, border: Border<T>) -> Result<Automaton<T>, MatrixError> {
+        if border == Border::Wrap && grid.size() == 0 {
+            return Err(MatrixError::Empty);
+        }
+
+        Ok(Self {
+            grid,
+            border,
+            generation: 0,
+            threads: 1,
+            buffer: vec![],
+        })
+    }
+
+    /// Computes the generations on `threads` threads, row by row
+    pub fn parallel(mut self, threads: usize) -> Automaton<T> {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Infinite background value, if the border is infinite
+    #[allow(dead_code)]
+    pub fn background(&self) -> Option<&T> {
+        match &self.border {
+            Border::Infinite(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Applies the rule to every cell at once, returns whether any cell changed
+    fn apply<F>(&mut self, rule: F) -> bool
+    where
+        F: Fn(&Neighbourhood<T>) -> T + Sync,
+    {
+        // The background evolves like any cell surrounded by background cells
+        let background = match &self.border {
+            Border::Infinite(background) => {
+                self.grid = self.grid.pad(1, background.clone());
+
+                let probe = Matrix::from_fn(1, 1, |_, _| background.clone());
+                Some(rule(&Neighbourhood {
+                    grid: &probe,
+                    border: &self.border,
+                    x: 0,
+                    y: 0,
+                }))
+            }
+            _ => None,
+        };
+
+        let mut buffer = mem::take(&mut self.buffer);
+        let next = |index| rule(&Neighbourhood::at(&self.grid, &self.border, index));
+        match self.threads {
+            1 => {
+                buffer.clear();
+                buffer.extend((0..self.grid.size()).map(next));
+            }
+            threads => parallel::collect_rows(
+                self.grid.width,
+                self.grid.height,
+                threads,
+                &mut buffer,
+                next,
+            ),
+        }
+
+        let changed = buffer != self.grid.data;
+        self.buffer = mem::replace(&mut self.grid.data, buffer);
+        if let Some(background) = background {
+            self.border = Border::Infinite(background);
+        }
+
+        changed
+    }
+
+    /// Advances one generation, returns whether any cell changed
+    #[allow(dead_code)]
+    pub fn step<F>(&mut self, rule: F) -> bool
+    where
+        F: Fn(&Neighbourhood<T>) -> T + Sync,
+    {
+        self.generation += 1;
+        self.apply(rule)
+    }
+
+    /// Advances one generation made of several synchronous phases applied in order,
+    /// every phase sees the result of the previous one
+    #[allow(dead_code)]
+    pub fn step_phases(&mut self, phases: &[&Rule<T>]) -> bool {
+        self.generation += 1;
+        phases
+            .iter()
+            .fold(false, |changed, phase| self.apply(phase) | changed)
+    }
+
+    #[allow(dead_code)]
+    pub fn run<F>(&mut self, steps: usize, rule: F)
+    where
+        F: Fn(&Neighbourhood<T>) -> T + Sync,
+    {
+        (0..steps).for_each(|_| {
+            self.step(&rule);
+        });
+    }
+
+    /// Steps until a generation leaves every cell unchanged,
+    /// returns the number of steps taken including the last one
+    #[allow(dead_code)]
+    pub fn run_until_stable<F>(&mut self, rule: F) -> usize
+    where
+        F: Fn(&Neighbourhood<T>) -> T + Sync,
+    {
+        (1..).find(|_| !self.step(&rule)).unwrap()
+    }
+
+    /// Same as `run_until_stable` for generations made of several phases
+    #[allow(dead_code)]
+    pub fn run_phases_until_stable(&mut self, phases: &[&Rule<T>]) -> usize {
+        (1..).find(|_| !self.step_phases(phases)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::automaton::{Automaton, Border, Neighbourhood};
+    use crate::matrix::diff::assert_matrix_eq;
+    use crate::matrix::Matrix;
+
+    fn life(neighbourhood: &Neighbourhood<u8>) -> u8 {
+        let alive = neighbourhood.neighbours8().filter(|&&v| v == 1).count();
+        match (neighbourhood.cell(), alive) {
+            (1, 2) | (_, 3) => 1,
+            _ => 0,
+        }
+    }
+
+    #[test]
+    fn automaton_fixed_border() {
+        let blinker = Matrix::<u8>::from("000\n111\n000").unwrap();
+        let mut automaton = Automaton::new(blinker, Border::Fixed(0)).unwrap();
+
+        assert!(automaton.step(life));
+        assert_eq!(automaton.grid.to_string(), "010\n010\n010\n");
+
+        automaton.run(3, life);
+        assert_eq!(automaton.grid.to_string(), "000\n111\n000\n");
+        assert_eq!(automaton.generation, 4);
+    }
+
+    #[test]
+    fn automaton_wrap_border() {
+        let glider = "010000\n001000\n111000\n000000\n000000\n000000\n";
+        let mut automaton =
+            Automaton::new(Matrix::<u8>::from(glider).unwrap(), Border::Wrap).unwrap();
+
+        // A glider moves by one cell diagonally every 4 steps, crossing the 6×6 torus in 24
+        automaton.run(4, life);
+        assert_eq!(automaton.grid.get(3, 3), Some(&1));
+        automaton.run(20, life);
+        assert_eq!(automaton.grid.to_string(), glider);
+
+        let empty = Matrix::<u8>::from_fn(0, 0, |_, _| 0);
+        assert!(Automaton::new(empty.clone(), Border::Wrap).is_err());
+        assert!(Automaton::new(empty, Border::Fixed(0)).is_ok());
+    }
+
+    #[test]
+    fn automaton_parallel() {
+        let grid = Matrix::from_fn(13, 11, |x, y| ((x * 7 + y * 3) % 5 == 0) as u8);
+        let mut serial = Automaton::new(grid.clone(), Border::Infinite(0)).unwrap();
+        let mut parallel = Automaton::new(grid, Border::Infinite(0))
+            .unwrap()
+            .parallel(4);
+
+        serial.run(5, life);
+        parallel.run(5, life);
+        assert_matrix_eq!(serial.grid, parallel.grid);
+    }
+
+    #[test]
+    fn automaton_infinite_border() {
+        let grid = Matrix::<u8>::from("1").unwrap();
+        let mut automaton = Automaton::new(grid, Border::Infinite(0)).unwrap();
+
+        // Every cell toggles, so does the background
+        automaton.step(|n| 1 - n.cell());
+        assert_eq!(automaton.grid.to_string(), "111\n101\n111\n");
+        assert_eq!(automaton.background(), Some(&1));
+    }
+
+    #[test]
+    fn automaton_phases_until_stable() {
+        let grid = Matrix::<char>::from(">.>.\n....").unwrap();
+        let mut automaton = Automaton::new(grid, Border::Fixed('#')).unwrap();
+
+        let east = |n: &Neighbourhood<char>| match (n.get(-1, 0), n.cell(), n.get(1, 0)) {
+            ('>', '.', _) => '>',
+            (_, '>', '.') => '.',
+            (_, &cell, _) => cell,
+        };
+        let down = |n: &Neighbourhood<char>| match (n.get(0, -1), n.cell()) {
+            ('>', '.') if n.position().0 == 3 => 'v',
+            (_, &cell) => cell,
+        };
+
+        assert_eq!(automaton.run_phases_until_stable(&[&east, &down]), 3);
+        assert_eq!(automaton.grid.to_string(), "..>>\n...v\n");
+    }
+}
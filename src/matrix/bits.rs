@@ -0,0 +1,364 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{BitAnd, BitOr, BitXor};
+
+use crate::matrix::Matrix;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Boolean matrix packing every row into 64-bit words, bit `x % 64` of word `x / 64`
+/// holds the cell `x`. Bits past the width are always kept zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitMatrix {
+    pub width: usize,
+    pub height: usize,
+    words_per_row: usize,
+    data: Vec<u64>,
+}
+
+/// Returns 64 cells of `row` starting at `start`, cells outside of the row are zero
+fn bits_at(row: &[u64], start: i64) -> u64 {
+    if start < 0 {
+        return match start {
+            start if start <= -(WORD_BITS as i64) => 0,
+            start => row.first().map(|word| word << -start).unwrap_or(0),
+        };
+    }
+
+    let word = start as usize / WORD_BITS;
+    let shift = start as usize % WORD_BITS;
+    let low = row.get(word).map(|word| word >> shift).unwrap_or(0);
+
+    match shift {
+        0 => low,
+        _ => {
+            low | row
+                .get(word + 1)
+                .map(|word| word << (WORD_BITS - shift))
+                .unwrap_or(0)
+        }
+    }
+}
+
+impl BitMatrix {
+    pub fn new(width: usize, height: usize) -> BitMatrix {
+        let words_per_row = width.div_ceil(WORD_BITS);
+
+        Self {
+            width,
+            height,
+            words_per_row,
+            data: vec![0; words_per_row * height],
+        }
+    }
+
+    pub fn from_matrix<T, F>(matrix: &Matrix<T>, predicate: F) -> BitMatrix
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut result = BitMatrix::new(matrix.width, matrix.height);
+        matrix
+            .iter()
+            .filter(|(value, _)| predicate(value))
+            .for_each(|(_, (x, y))| result.set(x, y, true));
+
+        result
+    }
+
+    pub fn to_matrix(&self) -> Matrix<bool> {
+        Matrix::from_fn(self.width, self.height, |x, y| self.get(x, y))
+    }
+
+    fn row(&self, y: usize) -> &[u64] {
+        &self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    fn row_mut(&mut self, y: usize) -> &mut [u64] {
+        &mut self.data[y * self.words_per_row..(y + 1) * self.words_per_row]
+    }
+
+    /// Clears the bits past the width in the last word of every row
+    fn mask_rows(&mut self) {
+        let unused = self.words_per_row * WORD_BITS - self.width;
+        if unused == 0 {
+            return;
+        }
+
+        let mask = u64::MAX >> unused;
+        for y in 0..self.height {
+            let last = (y + 1) * self.words_per_row - 1;
+            self.data[last] &= mask;
+        }
+    }
+
+    /// Builds a matrix where the row `y` is copied from the row returned by `rows(y)`,
+    /// starting at the returned cell offset
+    fn from_rows<'a, F>(width: usize, height: usize, rows: F) -> BitMatrix
+    where
+        F: Fn(usize) -> Option<(&'a [u64], i64)>,
+    {
+        let mut result = BitMatrix::new(width, height);
+        for y in 0..height {
+            if let Some((row, start)) = rows(y) {
+                result
+                    .row_mut(y)
+                    .iter_mut()
+                    .enumerate()
+                    .for_each(|(i, word)| *word = bits_at(row, start + (i * WORD_BITS) as i64));
+            }
+        }
+
+        result.mask_rows();
+        result
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+
+        self.row(y)[x / WORD_BITS] >> (x % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let word = &mut self.row_mut(y)[x / WORD_BITS];
+        let bit = 1 << (x % WORD_BITS);
+        match value {
+            true => *word |= bit,
+            false => *word &= !bit,
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.data
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn row_count_ones(&self, y: usize) -> usize {
+        self.row(y)
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// Moves every cell by `dx` columns and `dy` rows, cells moved in from outside are cleared
+    pub fn shift(&self, dx: i64, dy: i64) -> BitMatrix {
+        BitMatrix::from_rows(self.width, self.height, |y| {
+            let source = y as i64 - dy;
+            (source >= 0 && (source as usize) < self.height)
+                .then(|| (self.row(source as usize), -dx))
+        })
+    }
+
+    /// Mirrors the matrix left to right
+    pub fn flip_horizontal(&self) -> BitMatrix {
+        let reversed = self
+            .data
+            .chunks(self.words_per_row.max(1))
+            .flat_map(|row| row.iter().rev().map(|word| word.reverse_bits()))
+            .collect::<Vec<_>>();
+        let start = (self.words_per_row * WORD_BITS - self.width) as i64;
+
+        BitMatrix::from_rows(self.width, self.height, |y| {
+            Some((
+                &reversed[y * self.words_per_row..(y + 1) * self.words_per_row],
+                start,
+            ))
+        })
+    }
+
+    /// Mirrors the matrix top to bottom
+    pub fn flip_vertical(&self) -> BitMatrix {
+        BitMatrix::from_rows(self.width, self.height, |y| {
+            Some((self.row(self.height - y - 1), 0))
+        })
+    }
+
+    /// Folds the right part over the left part along the column `at`, merging cells with OR
+    pub fn fold_left(&self, at: usize) -> BitMatrix {
+        let width = at.max(self.width.saturating_sub(at + 1));
+        let left = BitMatrix::from_rows(width, self.height, |y| {
+            Some((self.row(y), at as i64 - width as i64))
+        });
+        let right = self.flip_horizontal();
+        let right = BitMatrix::from_rows(width, self.height, |y| {
+            Some((
+                right.row(y),
+                self.width as i64 - 1 - at as i64 - width as i64,
+            ))
+        });
+
+        &left | &right
+    }
+
+    /// Folds the bottom part over the top part along the row `at`, merging cells with OR
+    pub fn fold_up(&self, at: usize) -> BitMatrix {
+        let height = at.max(self.height.saturating_sub(at + 1));
+        let mut result = BitMatrix::new(self.width, height);
+
+        for y in 0..height {
+            let rows = [(at + y).checked_sub(height), Some(at + height - y)];
+            for source in rows.into_iter().flatten().filter(|&y| y < self.height) {
+                let (from, to) = (source * self.words_per_row, y * self.words_per_row);
+                for i in 0..self.words_per_row {
+                    result.data[to + i] |= self.data[from + i];
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Reads the 3×3 window centered on `(x, y)` as a 9-bit number, the top left cell
+    /// being the most significant bit. Cells outside of the matrix read as `outside`.
+    pub fn window3(&self, x: i64, y: i64, outside: bool) -> usize {
+        let valid = (0..3).fold(0u64, |mask, i| {
+            let position = x - 1 + i;
+            mask | ((position >= 0 && (position as usize) < self.width) as u64) << i
+        });
+
+        (y - 1..=y + 1).fold(0, |result, y| {
+            let bits = match y {
+                y if y >= 0 && (y as usize) < self.height => {
+                    bits_at(self.row(y as usize), x - 1) & valid
+                        | if outside { !valid & 0b111 } else { 0 }
+                }
+                _ if outside => 0b111,
+                _ => 0,
+            };
+
+            // Cell x - 1 is the lowest bit, but has to become the highest one
+            let bits = (bits & 1) << 2 | (bits & 2) | (bits >> 2 & 1);
+            result << 3 | bits as usize
+        })
+    }
+
+    fn combine<F>(&self, other: &BitMatrix, func: F) -> BitMatrix
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+
+        BitMatrix {
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(&a, &b)| func(a, b))
+                .collect(),
+            ..*self
+        }
+    }
+}
+
+impl BitAnd for &BitMatrix {
+    type Output = BitMatrix;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitMatrix {
+    type Output = BitMatrix;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitMatrix {
+    type Output = BitMatrix;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.combine(rhs, |a, b| a ^ b)
+    }
+}
+
+impl Display for BitMatrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                write!(f, "{}", if self.get(x, y) { '#' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::bits::BitMatrix;
+    use crate::matrix::Matrix;
+
+    fn bit_matrix(s: &str) -> BitMatrix {
+        BitMatrix::from_matrix(&Matrix::<char>::from(s).unwrap(), |&ch| ch == '#')
+    }
+
+    #[test]
+    fn bit_matrix_get_set() {
+        let mut matrix = BitMatrix::new(130, 2);
+        matrix.set(0, 0, true);
+        matrix.set(64, 0, true);
+        matrix.set(129, 1, true);
+        matrix.set(130, 1, true);
+
+        assert!(matrix.get(64, 0));
+        assert!(!matrix.get(63, 0));
+        assert_eq!(matrix.count_ones(), 3);
+        assert_eq!(matrix.row_count_ones(1), 1);
+
+        matrix.set(64, 0, false);
+        assert_eq!(matrix.count_ones(), 2);
+    }
+
+    #[test]
+    fn bit_matrix_ops() {
+        let a = bit_matrix("##..\n#.#.");
+        let b = bit_matrix(".#.#\n##..");
+
+        assert_eq!((&a & &b).to_string(), ".#..\n#...\n");
+        assert_eq!((&a | &b).to_string(), "##.#\n###.\n");
+        assert_eq!((&a ^ &b).to_string(), "#..#\n.##.\n");
+        assert_eq!(a.shift(1, 1).to_string(), "....\n.##.\n");
+        assert_eq!(a.shift(-1, 0).to_string(), "#...\n.#..\n");
+        assert_eq!(a.flip_horizontal().to_string(), "..##\n.#.#\n");
+        assert_eq!(a.flip_vertical().to_string(), "#.#.\n##..\n");
+    }
+
+    #[test]
+    fn bit_matrix_wide_flip_and_fold() {
+        let mut matrix = BitMatrix::new(131, 3);
+        matrix.set(0, 0, true);
+        matrix.set(70, 1, true);
+        matrix.set(130, 2, true);
+
+        let flipped = matrix.flip_horizontal();
+        assert!(flipped.get(130, 0) && flipped.get(60, 1) && flipped.get(0, 2));
+        assert_eq!(flipped.count_ones(), 3);
+
+        let folded = matrix.fold_left(65);
+        assert_eq!(folded.width, 65);
+        assert!(folded.get(0, 0) && folded.get(60, 1) && folded.get(0, 2));
+        assert_eq!(folded.count_ones(), 3);
+
+        let folded = bit_matrix("#..\n...\n.#.\n...\n..#").fold_up(2);
+        assert_eq!(folded.to_string(), "#.#\n...\n");
+    }
+
+    #[test]
+    fn bit_matrix_window() {
+        let matrix = bit_matrix("#..\n.#.\n..#");
+
+        assert_eq!(matrix.window3(1, 1, false), 0b100_010_001);
+        assert_eq!(matrix.window3(0, 0, false), 0b000_010_001);
+        assert_eq!(matrix.window3(0, 0, true), 0b111_110_101);
+    }
+}
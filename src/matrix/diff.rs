@@ -0,0 +1,238 @@
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::io::IsTerminal;
+
+use itertools::iproduct;
+
+use crate::matrix::Matrix;
+
+const HIGHLIGHT_BEFORE: &str = "\x1b[1;31m";
+const HIGHLIGHT_AFTER: &str = "\x1b[1;32m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStyle {
+    /// Changed cells are coloured, red before and green after
+    Ansi,
+    /// Changed cells are marked with `^` on the line below
+    Plain,
+}
+
+impl DiffStyle {
+    /// ANSI highlighting when stdout is a terminal, plain markers otherwise
+    #[allow(dead_code)]
+    pub fn detect() -> DiffStyle {
+        match std::io::stdout().is_terminal() {
+            true => DiffStyle::Ansi,
+            false => DiffStyle::Plain,
+        }
+    }
+}
+
+/// Cell that differs between two matrices, `None` when the cell is outside of one of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellChange<'a, T> {
+    pub position: (usize, usize),
+    pub before: Option<&'a T>,
+    pub after: Option<&'a T>,
+}
+
+/// Changed cells between two matrices, matrices of different sizes are compared over
+/// the largest of both
+pub struct GridDiff<'a, T> {
+    before: &'a Matrix<T>,
+    after: &'a Matrix<T>,
+    changes: Vec<CellChange<'a, T>>,
+}
+
+impl<'a, T: PartialEq> GridDiff<'a, T> {
+    #[allow(dead_code)]
+    pub fn new(before: &'a Matrix<T>, after: &'a Matrix<T>) -> GridDiff<'a, T> {
+        let width = before.width.max(after.width);
+        let height = before.height.max(after.height);
+
+        let changes = iproduct!(0..height, 0..width)
+            .filter_map(|(y, x)| {
+                let change = CellChange {
+                    position: (x, y),
+                    before: before.get(x, y),
+                    after: after.get(x, y),
+                };
+                (change.before != change.after).then_some(change)
+            })
+            .collect();
+
+        Self {
+            before,
+            after,
+            changes,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn changes(&self) -> &[CellChange<'a, T>] {
+        &self.changes
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<'a, T: Display> GridDiff<'a, T> {
+    /// Both matrices side by side under a ruler of the column numbers modulo 10,
+    /// rows are prefixed by their number
+    #[allow(dead_code)]
+    pub fn render(&self, style: DiffStyle) -> String {
+        let width = self.before.width.max(self.after.width);
+        let height = self.before.height.max(self.after.height);
+        let changed = self
+            .changes
+            .iter()
+            .map(|change| change.position)
+            .collect::<HashSet<_>>();
+
+        let text = |matrix: &Matrix<T>, x: usize, y: usize| {
+            matrix.get(x, y).map(|v| v.to_string()).unwrap_or_default()
+        };
+        let cell_width = iproduct!(0..height, 0..width)
+            .flat_map(|(y, x)| [text(self.before, x, y), text(self.after, x, y)])
+            .map(|cell| cell.chars().count())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let separator = if cell_width > 1 { " " } else { "" };
+        let label_width = height.saturating_sub(1).to_string().len();
+
+        let panel = |cell: &dyn Fn(usize) -> String| {
+            (0..width).map(cell).collect::<Vec<_>>().join(separator)
+        };
+        let line = |label: &str, left: String, right: String| {
+            format!("{:>label_width$} {} | {}\n", label, left, right)
+        };
+
+        let ruler = panel(&|x| format!("{:>cell_width$}", x % 10));
+        let mut result = format!(
+            "{} {} changed ({}×{} -> {}×{})\n",
+            self.changes.len(),
+            if self.changes.len() == 1 {
+                "cell"
+            } else {
+                "cells"
+            },
+            self.before.width,
+            self.before.height,
+            self.after.width,
+            self.after.height
+        );
+        result += &line("", ruler.clone(), ruler);
+
+        for y in 0..height {
+            let row = |matrix: &Matrix<T>, highlight: &str| {
+                panel(&|x| {
+                    let cell = format!("{:>cell_width$}", text(matrix, x, y));
+                    match style {
+                        DiffStyle::Ansi if changed.contains(&(x, y)) => {
+                            format!("{}{}{}", highlight, cell, RESET)
+                        }
+                        _ => cell,
+                    }
+                })
+            };
+            result += &line(
+                &y.to_string(),
+                row(self.before, HIGHLIGHT_BEFORE),
+                row(self.after, HIGHLIGHT_AFTER),
+            );
+
+            if style == DiffStyle::Plain && (0..width).any(|x| changed.contains(&(x, y))) {
+                let markers = panel(&|x| match changed.contains(&(x, y)) {
+                    true => format!("{:>cell_width$}", "^"),
+                    false => " ".repeat(cell_width),
+                });
+                result += &line("", markers.clone(), markers);
+            }
+        }
+
+        result
+    }
+}
+
+impl<'a, T: Display> Display for GridDiff<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.render(DiffStyle::detect()))
+    }
+}
+
+/// Asserts that two matrices are equal, the panic message shows a diff of both
+#[allow(unused_macros)]
+macro_rules! assert_matrix_eq {
+    ($left:expr, $right:expr $(,)?) => {{
+        let (left, right) = (&$left, &$right);
+        let diff = $crate::matrix::diff::GridDiff::new(left, right);
+        if !diff.is_empty() {
+            panic!("matrices are not equal\n{}", diff);
+        }
+    }};
+}
+
+#[allow(unused_imports)]
+pub(crate) use assert_matrix_eq;
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::diff::{DiffStyle, GridDiff};
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn diff_changes() {
+        let before = Matrix::<u8>::from("123\n456").unwrap();
+        let after = Matrix::<u8>::from("103\n456\n789").unwrap();
+
+        let diff = GridDiff::new(&before, &after);
+        let changes = diff
+            .changes()
+            .iter()
+            .map(|change| (change.position, change.before, change.after))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                ((1, 0), Some(&2), Some(&0)),
+                ((0, 2), None, Some(&7)),
+                ((1, 2), None, Some(&8)),
+                ((2, 2), None, Some(&9)),
+            ]
+        );
+
+        assert!(GridDiff::new(&before, &before.clone()).is_empty());
+    }
+
+    #[test]
+    fn diff_render() {
+        let before = Matrix::<char>::from("..>.\n.v..").unwrap();
+        let after = Matrix::<char>::from("...>\n.v..").unwrap();
+        let diff = GridDiff::new(&before, &after);
+
+        assert_eq!(
+            diff.render(DiffStyle::Plain),
+            "2 cells changed (4×2 -> 4×2)\n  0123 | 0123\n0 ..>. | ...>\n    ^^ |   ^^\n1 .v.. | .v..\n"
+        );
+
+        let ansi = diff.render(DiffStyle::Ansi);
+        assert!(ansi.contains("0 ..\x1b[1;31m>\x1b[0m\x1b[1;31m.\x1b[0m | "));
+        assert!(!ansi.contains('^'));
+    }
+
+    #[test]
+    fn diff_render_wide_cells() {
+        let before = Matrix::from_fn(2, 1, |x, _| x * 10);
+        let after = Matrix::from_fn(2, 1, |x, _| x * 11);
+
+        assert_eq!(
+            GridDiff::new(&before, &after).render(DiffStyle::Plain),
+            "1 cell changed (2×1 -> 2×1)\n   0  1 |  0  1\n0  0 10 |  0 11\n      ^ |     ^\n"
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+/// Dense N-dimensional grid, the first axis changes fastest in the underlying storage
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T, const N: usize> {
+    pub dimensions: [usize; N],
+    pub(crate) data: Vec<T>,
+}
+
+#[allow(dead_code)]
+pub type Grid3<T> = Grid<T, 3>;
+
+/// Offsets to the cells sharing a face with the origin (4 in 2D, 6 in 3D)
+#[allow(dead_code)]
+pub fn orthogonal_offsets<const N: usize>() -> Vec<[i32; N]> {
+    (0..N)
+        .flat_map(|axis| {
+            [1, -1].map(|delta| {
+                let mut offset = [0; N];
+                offset[axis] = delta;
+                offset
+            })
+        })
+        .collect()
+}
+
+/// Offsets to every cell touching the origin (8 in 2D, 26 in 3D)
+#[allow(dead_code)]
+pub fn all_offsets<const N: usize>() -> Vec<[i32; N]> {
+    (0..N)
+        .map(|_| -1..=1)
+        .multi_cartesian_product()
+        .filter(|offset| offset.iter().any(|&delta| delta != 0))
+        .map(|offset| offset.try_into().unwrap())
+        .collect()
+}
+
+fn offset_position<const N: usize>(position: [i32; N], offset: &[i32; N]) -> [i32; N] {
+    let mut result = position;
+    result
+        .iter_mut()
+        .zip(offset)
+        .for_each(|(value, delta)| *value += delta);
+
+    result
+}
+
+impl<T, const N: usize> Grid<T, N> {
+    #[allow(dead_code)]
+    pub fn new(dimensions: [usize; N]) -> Grid<T, N>
+    where
+        T: Default,
+    {
+        Self::from_fn(dimensions, |_| T::default())
+    }
+
+    #[allow(dead_code)]
+    pub fn from_fn<F>(dimensions: [usize; N], mut func: F) -> Grid<T, N>
+    where
+        F: FnMut([usize; N]) -> T,
+    {
+        let size = dimensions.iter().product();
+        let mut grid = Self {
+            dimensions,
+            data: Vec::with_capacity(size),
+        };
+
+        for index in 0..size {
+            let value = func(grid.position_of(index));
+            grid.data.push(value);
+        }
+
+        grid
+    }
+
+    #[allow(dead_code)]
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn position_of(&self, mut index: usize) -> [usize; N] {
+        let mut position = [0; N];
+        for (axis, &dimension) in self.dimensions.iter().enumerate() {
+            position[axis] = index % dimension;
+            index /= dimension;
+        }
+
+        position
+    }
+
+    fn get_index(&self, position: [i32; N]) -> Option<usize> {
+        position
+            .iter()
+            .zip(self.dimensions)
+            .rev()
+            .try_fold(0, |index, (&value, dimension)| {
+                (value >= 0 && (value as usize) < dimension)
+                    .then(|| index * dimension + value as usize)
+            })
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, position: [i32; N]) -> Option<&T> {
+        self.get_index(position).map(|index| &self.data[index])
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut(&mut self, position: [i32; N]) -> Option<&mut T> {
+        self.get_index(position).map(|index| &mut self.data[index])
+    }
+
+    #[allow(dead_code)]
+    pub fn set(&mut self, position: [i32; N], value: T) {
+        if let Some(index) = self.get_index(position) {
+            self.data[index] = value;
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&T, [usize; N])> {
+        self.data
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (value, self.position_of(index)))
+    }
+
+    #[allow(dead_code)]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&mut T, [usize; N])> {
+        let dimensions = self.dimensions;
+        self.data
+            .iter_mut()
+            .enumerate()
+            .map(move |(mut index, value)| {
+                let mut position = [0; N];
+                for (axis, &dimension) in dimensions.iter().enumerate() {
+                    position[axis] = index % dimension;
+                    index /= dimension;
+                }
+
+                (value, position)
+            })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_iter<'a>(
+        &'a self,
+        offsets: &'a [[i32; N]],
+        position: [i32; N],
+    ) -> impl Iterator<Item = (&'a T, [i32; N])> + 'a {
+        offsets.iter().filter_map(move |offset| {
+            let position = offset_position(position, offset);
+            self.get(position).map(|value| (value, position))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_orthogonal_iter(
+        &self,
+        position: [i32; N],
+    ) -> impl Iterator<Item = (&T, [i32; N])> {
+        orthogonal_offsets().into_iter().filter_map(move |offset| {
+            let position = offset_position(position, &offset);
+            self.get(position).map(|value| (value, position))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_all_iter(&self, position: [i32; N]) -> impl Iterator<Item = (&T, [i32; N])> {
+        all_offsets().into_iter().filter_map(move |offset| {
+            let position = offset_position(position, &offset);
+            self.get(position).map(|value| (value, position))
+        })
+    }
+}
+
+impl<T> Grid<T, 3> {
+    #[allow(dead_code)]
+    pub fn neighbours6_iter(&self, x: i32, y: i32, z: i32) -> impl Iterator<Item = (&T, [i32; 3])> {
+        self.neighbours_orthogonal_iter([x, y, z])
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours26_iter(
+        &self,
+        x: i32,
+        y: i32,
+        z: i32,
+    ) -> impl Iterator<Item = (&T, [i32; 3])> {
+        self.neighbours_all_iter([x, y, z])
+    }
+}
+
+/// Unbounded N-dimensional set of points
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PointSet<const N: usize> {
+    points: HashSet<[i32; N]>,
+}
+
+#[allow(dead_code)]
+pub type PointSet3 = PointSet<3>;
+
+impl<const N: usize> PointSet<N> {
+    #[allow(dead_code)]
+    pub fn new() -> PointSet<N> {
+        Self {
+            points: HashSet::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, position: [i32; N]) -> bool {
+        self.points.contains(&position)
+    }
+
+    #[allow(dead_code)]
+    pub fn insert(&mut self, position: [i32; N]) -> bool {
+        self.points.insert(position)
+    }
+
+    #[allow(dead_code)]
+    pub fn remove(&mut self, position: [i32; N]) -> bool {
+        self.points.remove(&position)
+    }
+
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = &[i32; N]> {
+        self.points.iter()
+    }
+
+    /// Inserts (or removes when `value` is false) every point of the box between
+    /// `min` and `max`, both inclusive
+    #[allow(dead_code)]
+    pub fn set_box(&mut self, min: [i32; N], max: [i32; N], value: bool) {
+        let points = (0..N)
+            .map(|axis| min[axis]..=max[axis])
+            .multi_cartesian_product()
+            .map(|position| -> [i32; N] { position.try_into().unwrap() });
+
+        if value {
+            self.points.extend(points);
+        } else {
+            points.for_each(|position| {
+                self.points.remove(&position);
+            });
+        }
+    }
+
+    /// Smallest box containing every point as `(min, max)`
+    #[allow(dead_code)]
+    pub fn bounds(&self) -> Option<([i32; N], [i32; N])> {
+        self.points.iter().fold(None, |bounds, position| {
+            let (mut min, mut max) = bounds.unwrap_or((*position, *position));
+            for axis in 0..N {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+
+            Some((min, max))
+        })
+    }
+
+    /// Neighbours of `position` which belong to the set
+    #[allow(dead_code)]
+    pub fn neighbours_iter<'a>(
+        &'a self,
+        offsets: &'a [[i32; N]],
+        position: [i32; N],
+    ) -> impl Iterator<Item = [i32; N]> + 'a {
+        offsets
+            .iter()
+            .map(move |offset| offset_position(position, offset))
+            .filter(|position| self.points.contains(position))
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_orthogonal_iter(
+        &self,
+        position: [i32; N],
+    ) -> impl Iterator<Item = [i32; N]> + '_ {
+        orthogonal_offsets()
+            .into_iter()
+            .map(move |offset| offset_position(position, &offset))
+            .filter(|position| self.points.contains(position))
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_all_iter(&self, position: [i32; N]) -> impl Iterator<Item = [i32; N]> + '_ {
+        all_offsets()
+            .into_iter()
+            .map(move |offset| offset_position(position, &offset))
+            .filter(|position| self.points.contains(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::grid::{all_offsets, orthogonal_offsets, Grid, Grid3, PointSet3};
+
+    #[test]
+    fn grid_offsets() {
+        assert_eq!(orthogonal_offsets::<2>().len(), 4);
+        assert_eq!(orthogonal_offsets::<3>().len(), 6);
+        assert_eq!(all_offsets::<2>().len(), 8);
+        assert_eq!(all_offsets::<3>().len(), 26);
+        assert_eq!(all_offsets::<4>().len(), 80);
+    }
+
+    #[test]
+    fn grid_get_set() {
+        let mut grid = Grid3::<u8>::new([2, 3, 4]);
+        assert_eq!(grid.size(), 24);
+
+        grid.set([1, 2, 3], 5);
+        assert_eq!(grid.get([1, 2, 3]), Some(&5));
+        assert_eq!(grid.get([2, 2, 3]), None);
+        assert_eq!(grid.get([-1, 0, 0]), None);
+
+        let (value, position) = grid.iter().last().unwrap();
+        assert_eq!((*value, position), (5, [1, 2, 3]));
+
+        grid.iter_mut()
+            .for_each(|(value, [x, y, z])| *value += (x + y + z) as u8);
+        assert_eq!(grid.get([1, 1, 1]), Some(&3));
+        assert_eq!(grid.neighbours6_iter(0, 0, 0).count(), 3);
+        assert_eq!(grid.neighbours26_iter(1, 1, 1).count(), 17);
+
+        let grid = Grid::<usize, 2>::from_fn([3, 2], |[x, y]| x + y * 3);
+        assert_eq!(grid.data, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn point_set() {
+        let mut set = PointSet3::new();
+        set.set_box([0, 0, 0], [2, 2, 2], true);
+        set.set_box([1, 1, 1], [3, 3, 3], false);
+
+        assert_eq!(set.len(), 19);
+        assert!(set.contains([0, 1, 2]));
+        assert!(!set.contains([2, 2, 2]));
+        assert_eq!(set.bounds(), Some(([0, 0, 0], [2, 2, 2])));
+        assert_eq!(set.neighbours_orthogonal_iter([0, 0, 0]).count(), 3);
+        assert_eq!(set.neighbours_all_iter([1, 1, 0]).count(), 13);
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Result, Write};
+use std::path::Path;
+
+use crate::matrix::Matrix;
+
+pub type Rgb = (u8, u8, u8);
+
+#[allow(dead_code)]
+pub const BLACK: Rgb = (0, 0, 0);
+#[allow(dead_code)]
+pub const WHITE: Rgb = (255, 255, 255);
+
+/// Maps `value` in `0..=max` onto a gray level, zero being black
+#[allow(dead_code)]
+pub fn gray(value: usize, max: usize) -> Rgb {
+    let level = (value.min(max) * 255 / max.max(1)) as u8;
+    (level, level, level)
+}
+
+/// Distinct colour for the `index`th label, hues are spread with the golden ratio so that
+/// consecutive labels stay far apart
+pub fn palette(index: usize) -> Rgb {
+    let hue = (index as f64 * 0.618_033_988_75).fract() * 6.0;
+    let (value, saturation) = (230.0, 0.65);
+    let chroma = value * saturation;
+    let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as usize {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+    let min = value - chroma;
+
+    ((r + min) as u8, (g + min) as u8, (b + min) as u8)
+}
+
+fn luminance((r, g, b): Rgb) -> u8 {
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Pbm,
+    Pgm,
+    Ppm,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn from_path(path: &Path) -> Option<ImageFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "pbm" => Some(ImageFormat::Pbm),
+            "pgm" => Some(ImageFormat::Pgm),
+            "ppm" => Some(ImageFormat::Ppm),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+impl<T> Matrix<T> {
+    /// Cells of the row `y`, every cell repeated `scale` times
+    fn scaled_row<'a, U, F>(
+        &'a self,
+        y: usize,
+        scale: usize,
+        func: &'a F,
+    ) -> impl Iterator<Item = U> + 'a
+    where
+        U: Copy + 'a,
+        F: Fn(&T) -> U,
+    {
+        self.data[y * self.width..(y + 1) * self.width]
+            .iter()
+            .flat_map(move |value| std::iter::repeat_n(func(value), scale))
+    }
+
+    /// Writes a binary PBM image, cells mapped to `true` are black
+    #[allow(dead_code)]
+    pub fn write_pbm<W, F>(&self, out: &mut W, scale: usize, func: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> bool,
+    {
+        let scale = scale.max(1);
+        writeln!(out, "P4\n{} {}", self.width * scale, self.height * scale)?;
+
+        for y in 0..self.height {
+            let row = self
+                .scaled_row(y, scale, &func)
+                .collect::<Vec<_>>()
+                .chunks(8)
+                .map(|bits| {
+                    bits.iter()
+                        .enumerate()
+                        .fold(0u8, |byte, (i, &bit)| byte | (bit as u8) << (7 - i))
+                })
+                .collect::<Vec<_>>();
+
+            for _ in 0..scale {
+                out.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a binary PGM image with 256 gray levels, zero being black
+    #[allow(dead_code)]
+    pub fn write_pgm<W, F>(&self, out: &mut W, scale: usize, func: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> u8,
+    {
+        let scale = scale.max(1);
+        writeln!(
+            out,
+            "P5\n{} {}\n255",
+            self.width * scale,
+            self.height * scale
+        )?;
+
+        for y in 0..self.height {
+            let row = self.scaled_row(y, scale, &func).collect::<Vec<_>>();
+            for _ in 0..scale {
+                out.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes a binary PPM image
+    #[allow(dead_code)]
+    pub fn write_ppm<W, F>(&self, out: &mut W, scale: usize, func: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        let scale = scale.max(1);
+        writeln!(
+            out,
+            "P6\n{} {}\n255",
+            self.width * scale,
+            self.height * scale
+        )?;
+
+        for y in 0..self.height {
+            let row = self
+                .scaled_row(y, scale, &func)
+                .flat_map(|(r, g, b)| [r, g, b])
+                .collect::<Vec<_>>();
+
+            for _ in 0..scale {
+                out.write_all(&row)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes an SVG image, cells are drawn as squares of `scale` pixels.
+    /// Horizontal runs of cells sharing the same colour are merged into one rectangle.
+    #[allow(dead_code)]
+    pub fn write_svg<W, F>(&self, out: &mut W, scale: usize, func: F) -> Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        let scale = scale.max(1);
+        let (width, height) = (self.width * scale, self.height * scale);
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" shape-rendering="crispEdges">"#,
+        )?;
+
+        for y in 0..self.height {
+            let colors = self.scaled_row(y, 1, &func).collect::<Vec<_>>();
+
+            let mut x = 0;
+            while x < colors.len() {
+                let length = colors[x..].iter().take_while(|&&c| c == colors[x]).count();
+                let (r, g, b) = colors[x];
+                writeln!(
+                    out,
+                    r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#{:02x}{:02x}{:02x}"/>"##,
+                    x * scale,
+                    y * scale,
+                    length * scale,
+                    scale,
+                    r,
+                    g,
+                    b
+                )?;
+
+                x += length;
+            }
+        }
+
+        writeln!(out, "</svg>")
+    }
+}
+
+impl Matrix<Rgb> {
+    /// Saves the matrix to `path`, the image format is picked from the file extension
+    #[allow(dead_code)]
+    pub fn save_image<P: AsRef<Path>>(&self, path: P, scale: usize) -> Result<()> {
+        let path = path.as_ref();
+        let format = ImageFormat::from_path(path).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("unsupported image format: {}", path.display()),
+            )
+        })?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        match format {
+            ImageFormat::Pbm => self.write_pbm(&mut out, scale, |&color| luminance(color) < 128),
+            ImageFormat::Pgm => self.write_pgm(&mut out, scale, |&color| luminance(color)),
+            ImageFormat::Ppm => self.write_ppm(&mut out, scale, |&color| color),
+            ImageFormat::Svg => self.write_svg(&mut out, scale, |&color| color),
+        }?;
+
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::matrix::image::{gray, ImageFormat};
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn image_pbm() {
+        let matrix = Matrix::<u8>::from("101\n010").unwrap();
+        let mut out = vec![];
+        matrix.write_pbm(&mut out, 1, |&v| v == 1).unwrap();
+        assert_eq!(out, b"P4\n3 2\n\xa0\x40");
+
+        let mut out = vec![];
+        matrix.write_pbm(&mut out, 3, |&v| v == 1).unwrap();
+        assert_eq!(&out[..7], b"P4\n9 6\n");
+        assert_eq!(
+            &out[7..],
+            b"\xe3\x80\xe3\x80\xe3\x80\x1c\x00\x1c\x00\x1c\x00"
+        );
+    }
+
+    #[test]
+    fn image_pgm_ppm() {
+        let matrix = Matrix::<u8>::from("09").unwrap();
+
+        let mut out = vec![];
+        matrix
+            .write_pgm(&mut out, 2, |&v| gray(v as usize, 9).0)
+            .unwrap();
+        assert_eq!(out, b"P5\n4 2\n255\n\x00\x00\xff\xff\x00\x00\xff\xff");
+
+        let mut out = vec![];
+        matrix.write_ppm(&mut out, 1, |&v| (v, 0, 255 - v)).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\x00\x00\xff\x09\x00\xf6");
+    }
+
+    #[test]
+    fn image_svg() {
+        let matrix = Matrix::<u8>::from("001\n111").unwrap();
+        let mut out = vec![];
+        matrix
+            .write_svg(&mut out, 10, |&v| gray(v as usize, 1))
+            .unwrap();
+
+        let svg = String::from_utf8(out).unwrap();
+        assert!(svg.starts_with("<svg") && svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="20" height="10" fill="#000000"/>"##));
+        assert!(svg.contains(r##"<rect x="20" y="0" width="10" height="10" fill="#ffffff"/>"##));
+        assert!(svg.contains(r##"<rect x="0" y="10" width="30" height="10" fill="#ffffff"/>"##));
+    }
+
+    #[test]
+    fn image_format() {
+        assert_eq!(
+            ImageFormat::from_path(Path::new("out/day9.PPM")),
+            Some(ImageFormat::Ppm)
+        );
+        assert_eq!(ImageFormat::from_path(Path::new("day9.png")), None);
+    }
+}
@@ -0,0 +1,803 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+pub mod automaton;
+pub mod bits;
+pub mod diff;
+pub mod grid;
+pub mod image;
+pub mod parallel;
+pub mod parse;
+pub mod snapshot;
+pub mod sparse;
+pub mod view;
+
+#[derive(Clone, PartialEq)]
+pub struct Matrix<T> {
+    pub width: usize,
+    pub height: usize,
+    pub(crate) data: Vec<T>,
+}
+
+#[allow(dead_code)]
+pub const MATRIX_NEIGHBOURS_4: [(i32, i32); 4] = [(1, 0), (0, 1), (0, -1), (-1, 0)];
+
+#[allow(dead_code)]
+pub const MATRIX_NEIGHBOURS_8: [(i32, i32); 8] = [
+    (1, 0),
+    (0, 1),
+    (0, -1),
+    (-1, 0),
+    (-1, -1),
+    (1, 1),
+    (-1, 1),
+    (1, -1),
+];
+
+/// Axes along which positions outside of the matrix wrap around to the opposite edge
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    None,
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+impl<T> Matrix<T> {
+    pub fn size(&self) -> usize {
+        self.width * self.height
+    }
+
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&T, (usize, usize))> {
+        self.data.iter().enumerate().map(|(index, value)| {
+            let x = index % self.width;
+            let y = index / self.width;
+
+            (value, (x, y))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&mut T, (usize, usize))> {
+        self.data.iter_mut().enumerate().map(|(index, value)| {
+            let x = index % self.width;
+            let y = index / self.width;
+
+            (value, (x, y))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn iter_with_self(&self) -> impl Iterator<Item = (&T, (usize, usize), &Matrix<T>)> {
+        self.data.iter().enumerate().map(move |(index, value)| {
+            let x = index % self.width;
+            let y = index / self.width;
+
+            (value, (x, y), self)
+        })
+    }
+}
+
+impl<T: Debug + Default> Debug for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Matrix({}x{})", self.width, self.height)?;
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                match self.get(x, y) {
+                    Some(x) => write!(f, "{:?}", x)?,
+                    None => write!(f, " ")?,
+                };
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Display + Default> Display for Matrix<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                match self.get(x, y) {
+                    Some(x) => write!(f, "{}", x)?,
+                    None => write!(f, " ")?,
+                };
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Matrix<T> {
+    #[allow(dead_code)]
+    pub fn new(width: usize, height: usize) -> Matrix<T>
+    where
+        T: Default,
+    {
+        let mut data = Vec::new();
+        data.resize_with(width * height, T::default);
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn from_iter<'a>(iter: impl Iterator<Item = &'a T>, width: usize) -> Matrix<T>
+    where
+        T: 'a + Default + Copy,
+    {
+        let mut data: Vec<T> = iter.copied().collect();
+        let mut height = data.len() / width;
+        if width * height < data.len() {
+            height += 1;
+        }
+
+        data.resize_with(width * height, T::default);
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    fn get_index<P>(&self, x: P, y: P) -> Option<usize>
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+        if x < 0 || x as usize >= self.width || y < 0 || y as usize >= self.height {
+            return None;
+        }
+        let position = x + y * self.width as i32;
+        Some(position as usize)
+    }
+
+    pub fn get<P>(&self, x: P, y: P) -> Option<&T>
+    where
+        P: TryInto<i32>,
+    {
+        self.get_index(x, y).and_then(|index| self.data.get(index))
+    }
+
+    pub fn get_mut<P>(&mut self, x: P, y: P) -> Option<&mut T>
+    where
+        P: TryInto<i32>,
+    {
+        self.get_index(x, y)
+            .and_then(|index| self.data.get_mut(index))
+    }
+
+    #[allow(dead_code)]
+    pub fn set<P>(&mut self, x: P, y: P, value: T)
+    where
+        P: TryInto<i32>,
+    {
+        if let Some(index) = self.get_index(x, y) {
+            self.data[index] = value;
+        }
+    }
+
+    pub fn neighbours4<P>(&self, x: P, y: P) -> Vec<&T>
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+
+        MATRIX_NEIGHBOURS_4
+            .iter()
+            .filter_map(|(dx, dy)| self.get(x + dx, y + dy))
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours8<P>(&self, x: P, y: P) -> Vec<&T>
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+
+        MATRIX_NEIGHBOURS_8
+            .iter()
+            .filter_map(|(dx, dy)| self.get(x + dx, y + dy))
+            .collect()
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_iter<'a, P>(
+        &'a self,
+        offsets: &'a [(i32, i32)],
+        x: P,
+        y: P,
+    ) -> impl Iterator<Item = (&'a T, (i32, i32))> + 'a
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+
+        offsets.iter().filter_map(move |(dx, dy)| {
+            self.get(x + dx, y + dy)
+                .map(|value| (value, (x + dx, y + dy)))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours8_iter<P>(&self, x: P, y: P) -> impl Iterator<Item = (&T, (i32, i32))>
+    where
+        P: TryInto<i32>,
+    {
+        self.neighbours_iter(&MATRIX_NEIGHBOURS_8, x, y)
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours4_iter<P>(&self, x: P, y: P) -> impl Iterator<Item = (&T, (i32, i32))>
+    where
+        P: TryInto<i32>,
+    {
+        self.neighbours_iter(&MATRIX_NEIGHBOURS_4, x, y)
+    }
+
+    /// Maps a position onto the matrix, wrapping the coordinates along the `wrap` axes
+    #[allow(dead_code)]
+    pub fn wrap_position<P>(&self, x: P, y: P, wrap: Wrap) -> Option<(usize, usize)>
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+
+        let (x, y) = match wrap {
+            _ if self.width == 0 || self.height == 0 => return None,
+            Wrap::None => (x, y),
+            Wrap::Horizontal => (x.rem_euclid(self.width as i32), y),
+            Wrap::Vertical => (x, y.rem_euclid(self.height as i32)),
+            Wrap::Both => (
+                x.rem_euclid(self.width as i32),
+                y.rem_euclid(self.height as i32),
+            ),
+        };
+
+        self.get_index(x, y).map(|_| (x as usize, y as usize))
+    }
+
+    #[allow(dead_code)]
+    pub fn get_wrapped<P>(&self, x: P, y: P) -> Option<&T>
+    where
+        P: TryInto<i32>,
+    {
+        self.wrap_position(x, y, Wrap::Both)
+            .and_then(|(x, y)| self.get(x, y))
+    }
+
+    #[allow(dead_code)]
+    pub fn get_wrapped_mut<P>(&mut self, x: P, y: P) -> Option<&mut T>
+    where
+        P: TryInto<i32>,
+    {
+        self.wrap_position(x, y, Wrap::Both)
+            .and_then(|(x, y)| self.get_mut(x, y))
+    }
+
+    #[allow(dead_code)]
+    pub fn set_wrapped<P>(&mut self, x: P, y: P, value: T)
+    where
+        P: TryInto<i32>,
+    {
+        if let Some((x, y)) = self.wrap_position(x, y, Wrap::Both) {
+            self.set(x, y, value);
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_wrapped_iter<'a, P>(
+        &'a self,
+        offsets: &'a [(i32, i32)],
+        x: P,
+        y: P,
+        wrap: Wrap,
+    ) -> impl Iterator<Item = (&'a T, (i32, i32))> + 'a
+    where
+        P: TryInto<i32>,
+    {
+        let x = x.try_into().ok().unwrap();
+        let y = y.try_into().ok().unwrap();
+
+        offsets.iter().filter_map(move |(dx, dy)| {
+            self.wrap_position(x + dx, y + dy, wrap)
+                .map(|(x, y)| (&self.data[x + y * self.width], (x as i32, y as i32)))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours8_wrapped_iter<P>(
+        &self,
+        x: P,
+        y: P,
+        wrap: Wrap,
+    ) -> impl Iterator<Item = (&T, (i32, i32))>
+    where
+        P: TryInto<i32>,
+    {
+        self.neighbours_wrapped_iter(&MATRIX_NEIGHBOURS_8, x, y, wrap)
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours4_wrapped_iter<P>(
+        &self,
+        x: P,
+        y: P,
+        wrap: Wrap,
+    ) -> impl Iterator<Item = (&T, (i32, i32))>
+    where
+        P: TryInto<i32>,
+    {
+        self.neighbours_wrapped_iter(&MATRIX_NEIGHBOURS_4, x, y, wrap)
+    }
+
+    /// Rotates the row `y` by `by` cells, positive values move cells to the right
+    #[allow(dead_code)]
+    pub fn roll_row(&mut self, y: usize, by: i32) {
+        if y >= self.height || self.width == 0 {
+            return;
+        }
+
+        let shift = by.rem_euclid(self.width as i32) as usize;
+        self.data[y * self.width..(y + 1) * self.width].rotate_right(shift);
+    }
+
+    /// Rotates the column `x` by `by` cells, positive values move cells down
+    #[allow(dead_code)]
+    pub fn roll_column(&mut self, x: usize, by: i32) {
+        if x >= self.width || self.height == 0 {
+            return;
+        }
+
+        let shift = by.rem_euclid(self.height as i32) as usize;
+        self.reverse_column(x, 0, self.height);
+        self.reverse_column(x, 0, shift);
+        self.reverse_column(x, shift, self.height);
+    }
+
+    fn reverse_column(&mut self, x: usize, from: usize, to: usize) {
+        for i in 0..(to - from) / 2 {
+            self.data
+                .swap(x + (from + i) * self.width, x + (to - i - 1) * self.width);
+        }
+    }
+
+    /// Rotates the whole matrix by `dx` columns and `dy` rows
+    #[allow(dead_code)]
+    pub fn roll(&mut self, dx: i32, dy: i32) {
+        if self.width == 0 || self.height == 0 {
+            return;
+        }
+
+        (0..self.height).for_each(|y| self.roll_row(y, dx));
+
+        let shift = dy.rem_euclid(self.height as i32) as usize;
+        self.data.rotate_right(shift * self.width);
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Moves the row `y` by `by` cells, cells shifted in from the edge are set to `fill`
+    #[allow(dead_code)]
+    pub fn shift_row(&mut self, y: usize, by: i32, fill: T) {
+        if y >= self.height {
+            return;
+        }
+
+        self.roll_row(y, by);
+
+        let count = (by.unsigned_abs() as usize).min(self.width);
+        let row = &mut self.data[y * self.width..(y + 1) * self.width];
+        match by {
+            by if by > 0 => row[..count].fill(fill),
+            _ => row[self.width - count..].fill(fill),
+        }
+    }
+
+    /// Moves the column `x` by `by` cells, cells shifted in from the edge are set to `fill`
+    #[allow(dead_code)]
+    pub fn shift_column(&mut self, x: usize, by: i32, fill: T) {
+        if x >= self.width {
+            return;
+        }
+
+        self.roll_column(x, by);
+
+        let count = (by.unsigned_abs() as usize).min(self.height);
+        let rows = match by {
+            by if by > 0 => 0..count,
+            _ => self.height - count..self.height,
+        };
+
+        rows.for_each(|y| self.data[x + y * self.width] = fill.clone());
+    }
+}
+
+impl<T> Matrix<T> {
+    #[allow(dead_code)]
+    pub fn render_to_string<F>(&self, renderer: F) -> String
+    where
+        F: Fn(Option<&T>) -> String,
+    {
+        let rows = (0..self.height)
+            .map(|y| (0..self.width).map(|x| renderer(self.get(x, y))).collect())
+            .collect::<Vec<Vec<_>>>();
+
+        rows.iter()
+            .map(|row| row.join(""))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[allow(dead_code)]
+    pub fn map<U, F>(&self, func: F) -> Matrix<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Matrix {
+            width: self.width,
+            height: self.height,
+            data: self.data.iter().map(func).collect(),
+        }
+    }
+
+    /// Combines the cells of two matrices of the same size one by one
+    #[allow(dead_code)]
+    pub fn zip_with<U, V, F>(&self, other: &Matrix<U>, func: F) -> Matrix<V>
+    where
+        F: Fn(&T, &U) -> V,
+    {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+
+        Matrix {
+            width: self.width,
+            height: self.height,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| func(a, b))
+                .collect(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn from_fn<F>(width: usize, height: usize, mut func: F) -> Matrix<T>
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let data = (0..width * height)
+            .map(|index| func(index % width, index / width))
+            .collect();
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn bounding_box<F>(&self, predicate: F) -> Option<((usize, usize), (usize, usize))>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.iter()
+            .filter(|(value, _)| predicate(value))
+            .fold(None, |bounds, (_, (x, y))| match bounds {
+                None => Some(((x, y), (x, y))),
+                Some(((min_x, min_y), (max_x, max_y))) => {
+                    Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+                }
+            })
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    fn value_at(&self, x: usize, y: usize) -> T {
+        self.data[x + y * self.width].clone()
+    }
+
+    #[allow(dead_code)]
+    pub fn transpose(&self) -> Matrix<T> {
+        Matrix::from_fn(self.height, self.width, |x, y| self.value_at(y, x))
+    }
+
+    /// Rotates the matrix clockwise by 90 degrees
+    #[allow(dead_code)]
+    pub fn rotate_90(&self) -> Matrix<T> {
+        Matrix::from_fn(self.height, self.width, |x, y| {
+            self.value_at(y, self.height - x - 1)
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_180(&self) -> Matrix<T> {
+        Matrix::from_fn(self.width, self.height, |x, y| {
+            self.value_at(self.width - x - 1, self.height - y - 1)
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn rotate_270(&self) -> Matrix<T> {
+        Matrix::from_fn(self.height, self.width, |x, y| {
+            self.value_at(self.width - y - 1, x)
+        })
+    }
+
+    /// Mirrors the matrix left to right
+    #[allow(dead_code)]
+    pub fn flip_horizontal(&self) -> Matrix<T> {
+        Matrix::from_fn(self.width, self.height, |x, y| {
+            self.value_at(self.width - x - 1, y)
+        })
+    }
+
+    /// Mirrors the matrix top to bottom
+    #[allow(dead_code)]
+    pub fn flip_vertical(&self) -> Matrix<T> {
+        Matrix::from_fn(self.width, self.height, |x, y| {
+            self.value_at(x, self.height - y - 1)
+        })
+    }
+
+    /// Folds the right part over the left part along the column `at`.
+    /// The column itself is dropped, overlapping cells are merged with `combine`.
+    #[allow(dead_code)]
+    pub fn fold_left<F>(&self, at: usize, combine: F) -> Matrix<T>
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        let width = at.max(self.width.saturating_sub(at + 1));
+
+        Matrix::from_fn(width, self.height, |x, y| {
+            let left = (at + x).checked_sub(width);
+            let right = Some(at + width - x).filter(|&right| right < self.width);
+
+            match (left, right) {
+                (Some(left), Some(right)) => combine(
+                    &self.data[left + y * self.width],
+                    &self.data[right + y * self.width],
+                ),
+                (Some(x), None) | (None, Some(x)) => self.value_at(x, y),
+                (None, None) => unreachable!(),
+            }
+        })
+    }
+
+    /// Folds the bottom part over the top part along the row `at`.
+    /// The row itself is dropped, overlapping cells are merged with `combine`.
+    #[allow(dead_code)]
+    pub fn fold_up<F>(&self, at: usize, combine: F) -> Matrix<T>
+    where
+        F: Fn(&T, &T) -> T,
+    {
+        self.transpose().fold_left(at, combine).transpose()
+    }
+
+    #[allow(dead_code)]
+    pub fn sub_matrix(&self, x: usize, y: usize, width: usize, height: usize) -> Matrix<T> {
+        assert!(x + width <= self.width && y + height <= self.height);
+
+        Matrix::from_fn(width, height, |dx, dy| self.value_at(x + dx, y + dy))
+    }
+
+    /// Crops the matrix to the smallest rectangle containing every cell matching `predicate`
+    #[allow(dead_code)]
+    pub fn crop<F>(&self, predicate: F) -> Option<Matrix<T>>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.bounding_box(predicate)
+            .map(|((min_x, min_y), (max_x, max_y))| {
+                self.sub_matrix(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1)
+            })
+    }
+
+    /// Surrounds the matrix with `size` cells of `value` on every side
+    #[allow(dead_code)]
+    pub fn pad(&self, size: usize, value: T) -> Matrix<T> {
+        Matrix::from_fn(
+            self.width + size * 2,
+            self.height + size * 2,
+            |x, y| match (x.checked_sub(size), y.checked_sub(size)) {
+                (Some(x), Some(y)) if x < self.width && y < self.height => self.value_at(x, y),
+                _ => value.clone(),
+            },
+        )
+    }
+
+    /// Repeats the matrix `columns` times horizontally and `rows` times vertically,
+    /// every value is passed through `func` together with the tile position
+    #[allow(dead_code)]
+    pub fn tile<F>(&self, columns: usize, rows: usize, func: F) -> Matrix<T>
+    where
+        F: Fn(&T, (usize, usize)) -> T,
+    {
+        Matrix::from_fn(self.width * columns, self.height * rows, |x, y| {
+            func(
+                &self.data[x % self.width + (y % self.height) * self.width],
+                (x / self.width, y / self.height),
+            )
+        })
+    }
+}
+
+impl<T: Default + FromStr> Matrix<T> {
+    #[allow(dead_code)]
+    pub fn from(s: &str) -> Option<Matrix<T>> {
+        let lines = s.lines().map(|l| l.to_string()).collect::<Vec<String>>();
+        let width = lines.first()?.len();
+        let height = lines.len();
+
+        let data = lines
+            .iter()
+            .flat_map(|line| {
+                line.chars()
+                    .map(|ch| ch.to_string().parse::<T>().unwrap_or_default())
+                    .collect::<Vec<T>>()
+            })
+            .collect::<Vec<T>>();
+
+        Some(Self {
+            width,
+            height,
+            data,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn from_separated(s: &str, pat: &str) -> Option<Matrix<T>> {
+        let lines = s.lines().map(|l| l.to_string()).collect::<Vec<String>>();
+        let width = lines.first()?.split(pat).count();
+        let height = lines.len();
+        let mut data: Vec<T> = vec![];
+
+        for line in lines {
+            data.extend(line.split(pat).map(|s| s.parse::<T>().unwrap_or_default()));
+        }
+
+        Some(Self {
+            width,
+            height,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::{Matrix, Wrap};
+
+    #[test]
+    fn matrix_iter() {
+        let matrix = Matrix::<u8>::from("123\n456").unwrap();
+        assert_eq!(matrix.width, 3);
+        assert_eq!(matrix.height, 2);
+
+        let result = matrix
+            .iter()
+            .take(3)
+            .map(|(&v, (x, y))| (v, (x, y)))
+            .collect::<Vec<(u8, (usize, usize))>>();
+
+        assert_eq!(
+            [
+                (1u8, (0usize, 0usize)),
+                (2u8, (1usize, 0usize)),
+                (3u8, (2usize, 0usize))
+            ]
+            .to_vec(),
+            result
+        );
+    }
+
+    #[test]
+    fn matrix_from_iter() {
+        let v: Vec<u8> = vec![1, 2, 3, 4, 5, 6, 7];
+        let matrix = Matrix::<u8>::from_iter(v.iter(), 3);
+
+        let v: Vec<_> = matrix.iter().map(|(&v, _)| v).collect();
+        assert_eq!(v, vec![1, 2, 3, 4, 5, 6, 7, 0, 0]);
+    }
+
+    #[test]
+    fn matrix_rotate_and_flip() {
+        let matrix = Matrix::<u8>::from("123\n456").unwrap();
+
+        assert_eq!(matrix.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(matrix.rotate_90().to_string(), "41\n52\n63\n");
+        assert_eq!(matrix.rotate_180().to_string(), "654\n321\n");
+        assert_eq!(matrix.rotate_270().to_string(), "36\n25\n14\n");
+        assert_eq!(matrix.flip_horizontal().to_string(), "321\n654\n");
+        assert_eq!(matrix.flip_vertical().to_string(), "456\n123\n");
+    }
+
+    #[test]
+    fn matrix_fold() {
+        let matrix = Matrix::<u8>::from("10001\n00000\n01010").unwrap();
+
+        let folded = matrix.fold_left(2, |a, b| a | b);
+        assert_eq!(folded.to_string(), "10\n00\n01\n");
+
+        let folded = matrix.fold_up(1, |a, b| a | b);
+        assert_eq!(folded.to_string(), "11011\n");
+
+        let folded = matrix.fold_left(1, |a, b| a + b);
+        assert_eq!(folded.to_string(), "101\n000\n010\n");
+    }
+
+    #[test]
+    fn matrix_crop_pad_tile() {
+        let matrix = Matrix::<u8>::from("000\n010\n011\n").unwrap();
+
+        let cropped = matrix.crop(|&v| v == 1).unwrap();
+        assert_eq!(cropped.to_string(), "10\n11\n");
+        assert!(matrix.crop(|&v| v == 2).is_none());
+
+        let padded = cropped.pad(1, 7);
+        assert_eq!(padded.to_string(), "7777\n7107\n7117\n7777\n");
+
+        let tiled = cropped.tile(2, 2, |&v, (i, j)| v + (i + j * 2) as u8);
+        assert_eq!(tiled.to_string(), "1021\n1122\n3243\n3344\n");
+    }
+
+    #[test]
+    fn matrix_wrapped() {
+        let matrix = Matrix::<u8>::from("123\n456").unwrap();
+
+        assert_eq!(matrix.get_wrapped(-1, 0), Some(&3));
+        assert_eq!(matrix.get_wrapped(3, 3), Some(&4));
+        assert_eq!(matrix.wrap_position(-1, 2, Wrap::Horizontal), None);
+        assert_eq!(matrix.wrap_position(-1, 2, Wrap::Vertical), None);
+        assert_eq!(matrix.wrap_position(1, 2, Wrap::Vertical), Some((1, 0)));
+
+        let neighbours = matrix
+            .neighbours4_wrapped_iter(0, 0, Wrap::Horizontal)
+            .map(|(&v, _)| v)
+            .collect::<Vec<_>>();
+        assert_eq!(neighbours, vec![2, 4, 3]);
+
+        let neighbours = matrix.neighbours8_wrapped_iter(0, 0, Wrap::Both).count();
+        assert_eq!(neighbours, 8);
+    }
+
+    #[test]
+    fn matrix_roll_and_shift() {
+        let mut matrix = Matrix::<u8>::from("123\n456\n789").unwrap();
+
+        matrix.roll_row(0, 1);
+        matrix.roll_column(2, -1);
+        assert_eq!(matrix.to_string(), "316\n459\n782\n");
+
+        matrix.roll(-1, 1);
+        assert_eq!(matrix.to_string(), "827\n163\n594\n");
+
+        matrix.shift_row(0, 2, 0);
+        matrix.shift_column(1, -1, 0);
+        assert_eq!(matrix.to_string(), "068\n193\n504\n");
+    }
+
+    #[test]
+    fn matrix_from_separated() {
+        let matrix = Matrix::<u32>::from_separated("10,200\n3,4", ",").unwrap();
+        assert_eq!((matrix.width, matrix.height), (2, 2));
+        assert_eq!(matrix.get(1, 0), Some(&200));
+    }
+}
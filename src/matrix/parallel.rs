@@ -0,0 +1,193 @@
+use std::panic;
+use std::thread;
+
+use crate::matrix::automaton::{Border, Neighbourhood};
+use crate::matrix::Matrix;
+
+/// Work for a thread, borrowing from the caller for at most `'a`
+pub(crate) type Task<'a> = Box<dyn FnOnce() + Send + 'a>;
+
+/// Number of threads used by the parallel matrix operations
+pub fn threads() -> usize {
+    thread::available_parallelism()
+        .map(|threads| threads.get())
+        .unwrap_or(1)
+}
+
+/// Runs every task on a scoped thread of its own and returns once all of them have finished,
+/// so that the tasks can borrow from the caller. A panic in a task is resumed on the calling
+/// thread.
+pub(crate) fn run_all(tasks: Vec<Task<'_>>) {
+    if tasks.len() <= 1 {
+        tasks.into_iter().for_each(|task| task());
+        return;
+    }
+
+    thread::scope(|scope| {
+        let handles = tasks
+            .into_iter()
+            .map(|task| scope.spawn(task))
+            .collect::<Vec<_>>();
+
+        let mut panicked = None;
+        for handle in handles {
+            if let Err(payload) = handle.join() {
+                panicked.get_or_insert(payload);
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+    });
+}
+
+/// Fills `out` with `func(index)` for every cell of a `width`×`height` matrix in index order,
+/// the rows are split into `threads` chunks computed on their own threads
+pub(crate) fn collect_rows<U, F>(
+    width: usize,
+    height: usize,
+    threads: usize,
+    out: &mut Vec<U>,
+    func: F,
+) where
+    U: Send,
+    F: Fn(usize) -> U + Sync,
+{
+    out.clear();
+
+    let size = width * height;
+    let chunk = height.div_ceil(threads.max(1)) * width;
+    if chunk == 0 {
+        return;
+    }
+
+    let func = &func;
+    let mut chunks = (0..size).step_by(chunk).map(|_| vec![]).collect::<Vec<_>>();
+    run_all(
+        chunks
+            .iter_mut()
+            .enumerate()
+            .map(|(i, values)| {
+                let start = i * chunk;
+                Box::new(move || {
+                    *values = (start..(start + chunk).min(size)).map(func).collect();
+                }) as Task<'_>
+            })
+            .collect(),
+    );
+
+    chunks.into_iter().for_each(|values| out.extend(values));
+}
+
+impl<T: Sync> Matrix<T> {
+    fn par_from_index<U, F>(&self, func: F) -> Matrix<U>
+    where
+        U: Send,
+        F: Fn(usize) -> U + Sync,
+    {
+        let mut data = vec![];
+        collect_rows(self.width, self.height, threads(), &mut data, func);
+
+        Matrix {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    /// Parallel counterpart of `Matrix::map`
+    #[allow(dead_code)]
+    pub fn par_map<U, F>(&self, func: F) -> Matrix<U>
+    where
+        U: Send,
+        F: Fn(&T) -> U + Sync,
+    {
+        self.par_from_index(|index| func(&self.data[index]))
+    }
+
+    /// Parallel counterpart of `Matrix::zip_with`
+    #[allow(dead_code)]
+    pub fn par_zip_with<U, V, F>(&self, other: &Matrix<U>, func: F) -> Matrix<V>
+    where
+        U: Sync,
+        V: Send,
+        F: Fn(&T, &U) -> V + Sync,
+    {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+
+        self.par_from_index(|index| func(&self.data[index], &other.data[index]))
+    }
+
+    /// Parallel counterpart of `Matrix::map_neighbourhood`
+    #[allow(dead_code)]
+    pub fn par_map_neighbourhood<U, F>(&self, border: &Border<T>, func: F) -> Matrix<U>
+    where
+        U: Send,
+        F: Fn(&Neighbourhood<T>) -> U + Sync,
+    {
+        self.par_from_index(|index| func(&Neighbourhood::at(self, border, index)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::automaton::Border;
+    use crate::matrix::parallel::{collect_rows, run_all, Task};
+    use crate::matrix::Matrix;
+    use std::panic;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn parallel_collect_rows() {
+        for threads in 1..=8 {
+            let mut out = vec![];
+            collect_rows(3, 7, threads, &mut out, |index| index * 2);
+            assert_eq!(out, (0..21).map(|index| index * 2).collect::<Vec<_>>());
+        }
+
+        let mut out = vec![1];
+        collect_rows(0, 5, 4, &mut out, |index| index);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn parallel_run_all() {
+        let total = AtomicUsize::new(0);
+        let tasks = (1..=10)
+            .map(|n| {
+                let total = &total;
+                Box::new(move || {
+                    total.fetch_add(n, Ordering::Relaxed);
+                }) as Task<'_>
+            })
+            .collect();
+        run_all(tasks);
+        assert_eq!(total.into_inner(), 55);
+
+        let result = panic::catch_unwind(|| {
+            run_all(vec![Box::new(|| ()), Box::new(|| panic!("task failed"))]);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let a = Matrix::from_fn(37, 23, |x, y| (x * 31 + y * 17) % 10);
+        let b = Matrix::from_fn(37, 23, |x, y| x * y);
+
+        assert_eq!(a.par_map(|v| v * 3), a.map(|v| v * 3));
+        assert_eq!(
+            a.par_zip_with(&b, |a, b| a + b),
+            a.zip_with(&b, |a, b| a + b)
+        );
+
+        for border in [Border::Fixed(0), Border::Wrap] {
+            let sum =
+                |n: &crate::matrix::automaton::Neighbourhood<usize>| n.neighbours8().sum::<usize>();
+            assert_eq!(
+                a.par_map_neighbourhood(&border, sum),
+                a.map_neighbourhood(&border, sum)
+            );
+        }
+    }
+}
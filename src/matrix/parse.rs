@@ -0,0 +1,220 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::matrix::Matrix;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatrixError {
+    Empty,
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidCell {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatrixError::Empty => write!(f, "matrix has no rows"),
+            MatrixError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} cells, found {}",
+                line, expected, found
+            ),
+            MatrixError::InvalidCell {
+                line,
+                column,
+                value,
+            } => write!(
+                f,
+                "line {}, column {}: invalid cell {:?}",
+                line, column, value
+            ),
+        }
+    }
+}
+
+impl Error for MatrixError {}
+
+/// Lines of the input along with their 1-based numbers, trailing blank lines are skipped
+fn rows(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let lines = s.lines().collect::<Vec<_>>();
+    let count = lines.len() - lines.iter().rev().take_while(|l| l.is_empty()).count();
+
+    lines
+        .into_iter()
+        .take(count)
+        .enumerate()
+        .map(|(i, l)| (i + 1, l))
+}
+
+impl<T> Matrix<T> {
+    fn from_rows<I>(rows: I) -> Result<Matrix<T>, MatrixError>
+    where
+        I: Iterator<Item = Result<(usize, Vec<T>), MatrixError>>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut data = vec![];
+
+        for row in rows {
+            let (line, row) = row?;
+            let expected = *width.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(MatrixError::RaggedRow {
+                    line,
+                    expected,
+                    found: row.len(),
+                });
+            }
+
+            data.extend(row);
+            height += 1;
+        }
+
+        match width {
+            None | Some(0) => Err(MatrixError::Empty),
+            Some(width) => Ok(Self {
+                width,
+                height,
+                data,
+            }),
+        }
+    }
+
+    /// Parses one cell per character, `func` maps a character to a cell value
+    #[allow(dead_code)]
+    pub fn parse_with<F>(s: &str, func: F) -> Result<Matrix<T>, MatrixError>
+    where
+        F: Fn(char) -> Option<T>,
+    {
+        Self::from_rows(rows(s).map(|(line, row)| {
+            let cells = row
+                .chars()
+                .enumerate()
+                .map(|(column, ch)| {
+                    func(ch).ok_or_else(|| MatrixError::InvalidCell {
+                        line,
+                        column: column + 1,
+                        value: ch.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<T>, _>>()?;
+
+            Ok((line, cells))
+        }))
+    }
+
+    /// Parses one cell per token, tokens are separated by `separator` or by whitespace
+    /// when no separator is given. `func` maps a token to a cell value.
+    #[allow(dead_code)]
+    pub fn parse_tokens_with<F>(
+        s: &str,
+        separator: Option<&str>,
+        func: F,
+    ) -> Result<Matrix<T>, MatrixError>
+    where
+        F: Fn(&str) -> Option<T>,
+    {
+        Self::from_rows(rows(s).map(|(line, row)| {
+            let tokens: Box<dyn Iterator<Item = &str>> = match separator {
+                Some(separator) => Box::new(row.split(separator).map(str::trim)),
+                None => Box::new(row.split_whitespace()),
+            };
+
+            let cells = tokens
+                .enumerate()
+                .map(|(column, token)| {
+                    func(token).ok_or_else(|| MatrixError::InvalidCell {
+                        line,
+                        column: column + 1,
+                        value: token.to_string(),
+                    })
+                })
+                .collect::<Result<Vec<T>, _>>()?;
+
+            Ok((line, cells))
+        }))
+    }
+}
+
+impl<T: FromStr> Matrix<T> {
+    /// Validating counterpart of `Matrix::from`
+    #[allow(dead_code)]
+    pub fn parse(s: &str) -> Result<Matrix<T>, MatrixError> {
+        Self::parse_with(s, |ch| ch.to_string().parse().ok())
+    }
+
+    /// Validating counterpart of `Matrix::from_separated`
+    #[allow(dead_code)]
+    pub fn parse_separated(s: &str, separator: Option<&str>) -> Result<Matrix<T>, MatrixError> {
+        Self::parse_tokens_with(s, separator, |token| token.parse().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::parse::MatrixError;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn matrix_parse() {
+        let matrix = Matrix::<u8>::parse("123\r\n456\r\n\n").unwrap();
+        assert_eq!((matrix.width, matrix.height), (3, 2));
+        assert_eq!(matrix.data, vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(
+            Matrix::<u8>::parse("123\n45\n678").unwrap_err(),
+            MatrixError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            Matrix::<u8>::parse("123\n4x6").unwrap_err(),
+            MatrixError::InvalidCell {
+                line: 2,
+                column: 2,
+                value: "x".to_string()
+            }
+        );
+        assert_eq!(Matrix::<u8>::parse("\n\n").unwrap_err(), MatrixError::Empty);
+    }
+
+    #[test]
+    fn matrix_parse_with() {
+        let matrix = Matrix::parse_with("#.\n.#", |ch| match ch {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(matrix.data, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn matrix_parse_separated() {
+        let matrix = Matrix::<u32>::parse_separated("22 13  17\n 8  2 123", None).unwrap();
+        assert_eq!((matrix.width, matrix.height), (3, 2));
+        assert_eq!(matrix.get(2, 1), Some(&123));
+
+        let matrix = Matrix::<u32>::parse_separated("10, 20\n30, 40", Some(",")).unwrap();
+        assert_eq!(matrix.data, vec![10, 20, 30, 40]);
+
+        let error = Matrix::<u32>::parse_separated("1 2\n3 4 5", None).unwrap_err();
+        assert_eq!(error.to_string(), "line 2: expected 2 cells, found 3");
+    }
+}
@@ -0,0 +1,351 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Result, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::matrix::Matrix;
+
+const TEXT_HEADER: &str = "matrix";
+const BINARY_HEADER: &[u8; 4] = b"MTRX";
+
+/// Fixed-size little-endian encoding of a cell, used by binary snapshots
+pub trait BinaryCell: Sized {
+    const SIZE: usize;
+
+    fn write_bytes(&self, out: &mut Vec<u8>);
+    fn read_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! binary_cell {
+    ($( $type:ty ), *) => {$(
+        impl BinaryCell for $type {
+            const SIZE: usize = std::mem::size_of::<$type>();
+
+            fn write_bytes(&self, out: &mut Vec<u8>) {
+                out.extend(self.to_le_bytes());
+            }
+
+            fn read_bytes(bytes: &[u8]) -> Option<Self> {
+                Some(Self::from_le_bytes(bytes.try_into().ok()?))
+            }
+        }
+    )*};
+}
+
+binary_cell!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl BinaryCell for usize {
+    const SIZE: usize = u64::SIZE;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        (*self as u64).write_bytes(out)
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        u64::read_bytes(bytes)?.try_into().ok()
+    }
+}
+
+impl BinaryCell for bool {
+    const SIZE: usize = 1;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8)
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0] => Some(false),
+            [1] => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl BinaryCell for char {
+    const SIZE: usize = u32::SIZE;
+
+    fn write_bytes(&self, out: &mut Vec<u8>) {
+        (*self as u32).write_bytes(out)
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        char::from_u32(u32::read_bytes(bytes)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    Text,
+    Binary,
+}
+
+impl SnapshotFormat {
+    pub fn from_path(path: &Path) -> Option<SnapshotFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "txt" => Some(SnapshotFormat::Text),
+            "bin" => Some(SnapshotFormat::Binary),
+            _ => None,
+        }
+    }
+}
+
+fn invalid_data<E: Display>(error: E) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+fn format_of(path: &Path) -> Result<SnapshotFormat> {
+    SnapshotFormat::from_path(path).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("unsupported snapshot format: {}", path.display()),
+        )
+    })
+}
+
+impl<T: Display> Matrix<T> {
+    /// Writes a `matrix <width> <height> <step>` header followed by one line per row,
+    /// cells are separated by a single space
+    #[allow(dead_code)]
+    pub fn write_text<W: Write>(&self, out: &mut W, step: usize) -> Result<()> {
+        writeln!(
+            out,
+            "{} {} {} {}",
+            TEXT_HEADER, self.width, self.height, step
+        )?;
+
+        for y in 0..self.height {
+            let cells = self.data[y * self.width..(y + 1) * self.width]
+                .iter()
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>();
+
+            if let Some(cell) = cells
+                .iter()
+                .find(|cell| cell.is_empty() || cell.contains(char::is_whitespace))
+            {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("cell {:?} can't be written as a token", cell),
+                ));
+            }
+
+            writeln!(out, "{}", cells.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: FromStr> Matrix<T> {
+    /// Reads a matrix and its step written by `Matrix::write_text`
+    #[allow(dead_code)]
+    pub fn read_text<R: Read>(input: &mut R) -> Result<(Matrix<T>, usize)> {
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+
+        let (header, body) = text.split_once('\n').unwrap_or((&text, ""));
+        let (width, height, step) = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            [TEXT_HEADER, width, height, step] => (
+                width.parse::<usize>().map_err(invalid_data)?,
+                height.parse::<usize>().map_err(invalid_data)?,
+                step.parse::<usize>().map_err(invalid_data)?,
+            ),
+            _ => return Err(invalid_data(format!("invalid header {:?}", header))),
+        };
+
+        let size = width
+            .checked_mul(height)
+            .ok_or_else(|| invalid_data("matrix is too large"))?;
+        if size == 0 {
+            return match body.trim().is_empty() {
+                true => Ok((
+                    Matrix {
+                        width,
+                        height,
+                        data: vec![],
+                    },
+                    step,
+                )),
+                false => Err(invalid_data("unexpected cells in an empty matrix")),
+            };
+        }
+
+        let matrix = Matrix::parse_separated(body, None)
+            .map_err(|e| invalid_data(format!("after the header, {}", e)))?;
+        if (matrix.width, matrix.height) != (width, height) {
+            return Err(invalid_data(format!(
+                "expected {}×{} cells, found {}×{}",
+                width, height, matrix.width, matrix.height
+            )));
+        }
+
+        Ok((matrix, step))
+    }
+}
+
+impl<T: BinaryCell> Matrix<T> {
+    /// Writes the `MTRX` magic, the width, the height and the step as little-endian 64-bit
+    /// numbers, then every cell row by row
+    #[allow(dead_code)]
+    pub fn write_binary<W: Write>(&self, out: &mut W, step: usize) -> Result<()> {
+        let mut bytes = BINARY_HEADER.to_vec();
+        (self.width as u64).write_bytes(&mut bytes);
+        (self.height as u64).write_bytes(&mut bytes);
+        (step as u64).write_bytes(&mut bytes);
+        self.data
+            .iter()
+            .for_each(|value| value.write_bytes(&mut bytes));
+
+        out.write_all(&bytes)
+    }
+
+    /// Reads a matrix and its step written by `Matrix::write_binary`
+    #[allow(dead_code)]
+    pub fn read_binary<R: Read>(input: &mut R) -> Result<(Matrix<T>, usize)> {
+        let mut bytes = vec![];
+        input.read_to_end(&mut bytes)?;
+
+        let (header, body) = bytes.split_at(BINARY_HEADER.len().min(bytes.len()));
+        if header != BINARY_HEADER || body.len() < 24 {
+            return Err(invalid_data("invalid binary snapshot header"));
+        }
+
+        let size = |bytes: &[u8]| {
+            usize::read_bytes(bytes).ok_or_else(|| invalid_data("matrix is too large"))
+        };
+        let (width, height) = (size(&body[..8])?, size(&body[8..16])?);
+        let step = size(&body[16..24])?;
+        let cells = &body[24..];
+
+        if Some(cells.len())
+            != width
+                .checked_mul(height)
+                .and_then(|n| n.checked_mul(T::SIZE))
+        {
+            return Err(invalid_data(format!(
+                "expected {}×{} cells, found {} bytes",
+                width,
+                height,
+                cells.len()
+            )));
+        }
+
+        let data = cells
+            .chunks(T::SIZE.max(1))
+            .enumerate()
+            .map(|(index, bytes)| {
+                T::read_bytes(bytes)
+                    .ok_or_else(|| invalid_data(format!("invalid cell at index {}", index)))
+            })
+            .collect::<Result<Vec<T>>>()?;
+
+        Ok((
+            Matrix {
+                width,
+                height,
+                data,
+            },
+            step,
+        ))
+    }
+}
+
+impl<T: Display + FromStr + BinaryCell> Matrix<T> {
+    /// Saves the matrix reached after `step` steps to `path`, `.txt` files are text snapshots
+    /// and `.bin` files binary ones
+    #[allow(dead_code)]
+    pub fn save_snapshot<P: AsRef<Path>>(&self, path: P, step: usize) -> Result<()> {
+        let path = path.as_ref();
+        let format = format_of(path)?;
+
+        let mut out = BufWriter::new(File::create(path)?);
+        match format {
+            SnapshotFormat::Text => self.write_text(&mut out, step),
+            SnapshotFormat::Binary => self.write_binary(&mut out, step),
+        }?;
+
+        out.flush()
+    }
+
+    /// Loads a matrix and its step saved by `Matrix::save_snapshot`
+    #[allow(dead_code)]
+    pub fn load_snapshot<P: AsRef<Path>>(path: P) -> Result<(Matrix<T>, usize)> {
+        let path = path.as_ref();
+        let format = format_of(path)?;
+
+        let mut input = BufReader::new(File::open(path)?);
+        match format {
+            SnapshotFormat::Text => Matrix::read_text(&mut input),
+            SnapshotFormat::Binary => Matrix::read_binary(&mut input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn snapshot_text() {
+        let matrix = Matrix::from_fn(3, 2, |x, y| (x * 100 + y) as i32 - 1);
+
+        let mut out = vec![];
+        matrix.write_text(&mut out, 7).unwrap();
+        assert_eq!(out, b"matrix 3 2 7\n-1 99 199\n0 100 200\n");
+        assert_eq!(
+            Matrix::<i32>::read_text(&mut &out[..]).unwrap(),
+            (matrix, 7)
+        );
+
+        let empty = Matrix::<u8>::from_fn(0, 4, |_, _| 0);
+        let mut out = vec![];
+        empty.write_text(&mut out, 0).unwrap();
+        assert_eq!(Matrix::<u8>::read_text(&mut &out[..]).unwrap(), (empty, 0));
+
+        let error = Matrix::<u8>::read_text(&mut &b"matrix 2 2 0\n1 2\n3 4\n5 6\n"[..]);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "expected 2×2 cells, found 2×3"
+        );
+        assert!(Matrix::<u8>::read_text(&mut &b"grid 1 1 0\n1\n"[..]).is_err());
+        assert!(Matrix::<u8>::read_text(&mut &b"matrix 1 1\n1\n"[..]).is_err());
+        let huge = format!("matrix {} 2 0\n", usize::MAX);
+        assert_eq!(
+            Matrix::<u8>::read_text(&mut huge.as_bytes())
+                .unwrap_err()
+                .to_string(),
+            "matrix is too large"
+        );
+        assert!(Matrix::from_fn(1, 1, |_, _| ' ')
+            .write_text(&mut vec![], 0)
+            .is_err());
+    }
+
+    #[test]
+    fn snapshot_binary() {
+        let matrix = Matrix::<char>::from("v..>\n>.v.").unwrap();
+
+        let mut out = vec![];
+        matrix.write_binary(&mut out, 58).unwrap();
+        assert_eq!(out.len(), 4 + 24 + 8 * 4);
+        assert_eq!(
+            Matrix::<char>::read_binary(&mut &out[..]).unwrap(),
+            (matrix, 58)
+        );
+
+        assert!(Matrix::<char>::read_binary(&mut &out[..out.len() - 1]).is_err());
+        assert!(Matrix::<u32>::read_binary(&mut &out[1..]).is_err());
+
+        let flags = Matrix::from_fn(5, 3, |x, y| (x + y) % 2 == 0);
+        let mut out = vec![];
+        flags.write_binary(&mut out, 0).unwrap();
+        assert_eq!(
+            Matrix::<bool>::read_binary(&mut &out[..]).unwrap(),
+            (flags, 0)
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashMap;
+
+use crate::matrix::{Matrix, MATRIX_NEIGHBOURS_4, MATRIX_NEIGHBOURS_8};
+
+/// Unbounded grid that only stores the cells which differ from the background value
+#[derive(Debug, Clone)]
+pub struct SparseMatrix<T> {
+    background: T,
+    cells: HashMap<(i32, i32), T>,
+}
+
+impl<T> SparseMatrix<T> {
+    #[allow(dead_code)]
+    pub fn new(background: T) -> SparseMatrix<T> {
+        Self {
+            background,
+            cells: HashMap::new(),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    /// Number of cells which differ from the background
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, x: i32, y: i32) -> &T {
+        self.cells.get(&(x, y)).unwrap_or(&self.background)
+    }
+
+    /// Iterates over the stored cells in no particular order
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&T, (i32, i32))> {
+        self.cells
+            .iter()
+            .map(|(&position, value)| (value, position))
+    }
+
+    /// Smallest rectangle containing every stored cell as `((min_x, min_y), (max_x, max_y))`
+    #[allow(dead_code)]
+    pub fn bounds(&self) -> Option<((i32, i32), (i32, i32))> {
+        self.cells
+            .keys()
+            .fold(None, |bounds, &(x, y)| match bounds {
+                None => Some(((x, y), (x, y))),
+                Some(((min_x, min_y), (max_x, max_y))) => {
+                    Some(((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y))))
+                }
+            })
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours_iter<'a>(
+        &'a self,
+        offsets: &'a [(i32, i32)],
+        x: i32,
+        y: i32,
+    ) -> impl Iterator<Item = (&'a T, (i32, i32))> + 'a {
+        offsets
+            .iter()
+            .map(move |(dx, dy)| (self.get(x + dx, y + dy), (x + dx, y + dy)))
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours4_iter(&self, x: i32, y: i32) -> impl Iterator<Item = (&T, (i32, i32))> {
+        self.neighbours_iter(&MATRIX_NEIGHBOURS_4, x, y)
+    }
+
+    #[allow(dead_code)]
+    pub fn neighbours8_iter(&self, x: i32, y: i32) -> impl Iterator<Item = (&T, (i32, i32))> {
+        self.neighbours_iter(&MATRIX_NEIGHBOURS_8, x, y)
+    }
+}
+
+impl<T: Clone + PartialEq> SparseMatrix<T> {
+    #[allow(dead_code)]
+    pub fn set(&mut self, x: i32, y: i32, value: T) {
+        if value == self.background {
+            self.cells.remove(&(x, y));
+        } else {
+            self.cells.insert((x, y), value);
+        }
+    }
+
+    /// Replaces the value at the position with the result of `func`
+    #[allow(dead_code)]
+    pub fn update<F>(&mut self, x: i32, y: i32, func: F)
+    where
+        F: FnOnce(&T) -> T,
+    {
+        let value = func(self.get(x, y));
+        self.set(x, y, value);
+    }
+
+    /// Changes the background, cells equal to the new background stop being stored
+    #[allow(dead_code)]
+    pub fn set_background(&mut self, background: T) {
+        self.background = background;
+
+        let background = &self.background;
+        self.cells.retain(|_, value| value != background);
+    }
+
+    /// Applies `func` to every stored cell and to the background
+    #[allow(dead_code)]
+    pub fn map<U, F>(&self, func: F) -> SparseMatrix<U>
+    where
+        U: Clone + PartialEq,
+        F: Fn(&T) -> U,
+    {
+        let mut result = SparseMatrix::new(func(&self.background));
+        self.iter()
+            .for_each(|(value, (x, y))| result.set(x, y, func(value)));
+
+        result
+    }
+
+    /// Builds a sparse copy of `matrix` placing its top left corner at `origin`
+    #[allow(dead_code)]
+    pub fn from_matrix(matrix: &Matrix<T>, origin: (i32, i32), background: T) -> SparseMatrix<T> {
+        let mut result = SparseMatrix::new(background);
+        matrix.iter().for_each(|(value, (x, y))| {
+            result.set(x as i32 + origin.0, y as i32 + origin.1, value.clone())
+        });
+
+        result
+    }
+
+    /// Converts the bounding box of the stored cells into a dense matrix.
+    /// Returns the matrix along with the position of its top left corner.
+    #[allow(dead_code)]
+    pub fn to_matrix(&self) -> (Matrix<T>, (i32, i32)) {
+        let ((min_x, min_y), (max_x, max_y)) = match self.bounds() {
+            None => {
+                return (
+                    Matrix::from_fn(0, 0, |_, _| self.background.clone()),
+                    (0, 0),
+                )
+            }
+            Some(bounds) => bounds,
+        };
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let matrix = Matrix::from_fn(width, height, |x, y| {
+            self.get(x as i32 + min_x, y as i32 + min_y).clone()
+        });
+
+        (matrix, (min_x, min_y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::sparse::SparseMatrix;
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn sparse_matrix_get_set() {
+        let mut matrix = SparseMatrix::new(0);
+        matrix.set(-5, 3, 1);
+        matrix.update(2, -1, |v| v + 2);
+        matrix.update(2, -1, |v| v + 2);
+
+        assert_eq!(matrix.get(-5, 3), &1);
+        assert_eq!(matrix.get(2, -1), &4);
+        assert_eq!(matrix.get(100, 100), &0);
+        assert_eq!(matrix.bounds(), Some(((-5, -1), (2, 3))));
+
+        matrix.set(-5, 3, 0);
+        assert_eq!(matrix.len(), 1);
+
+        let neighbours = matrix.neighbours8_iter(1, 0).map(|(&v, _)| v).sum::<i32>();
+        assert_eq!(neighbours, 4);
+    }
+
+    #[test]
+    fn sparse_matrix_background() {
+        let mut matrix = SparseMatrix::new(false);
+        matrix.set(0, 0, true);
+        matrix.set(1, 1, true);
+
+        let inverted = matrix.map(|v| !v);
+        assert_eq!(inverted.background(), &true);
+        assert_eq!(inverted.get(0, 0), &false);
+        assert_eq!(inverted.get(7, 7), &true);
+        assert_eq!(inverted.len(), 2);
+
+        matrix.set_background(true);
+        assert!(matrix.is_empty());
+    }
+
+    #[test]
+    fn sparse_matrix_to_dense() {
+        let dense = Matrix::<u8>::from("010\n000\n001").unwrap();
+        let sparse = SparseMatrix::from_matrix(&dense, (-1, -1), 0);
+
+        assert_eq!(sparse.len(), 2);
+        assert_eq!(sparse.get(0, -1), &1);
+
+        let (matrix, origin) = sparse.to_matrix();
+        assert_eq!(origin, (0, -1));
+        assert_eq!(matrix.to_string(), "10\n00\n01\n");
+    }
+}
@@ -0,0 +1,308 @@
+use crate::matrix::Matrix;
+
+/// Borrowed rectangular part of a matrix, positions are relative to its top left corner
+pub struct SubMatrix<'a, T> {
+    matrix: &'a Matrix<T>,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Mutably borrowed rectangular part of a matrix
+pub struct SubMatrixMut<'a, T> {
+    matrix: &'a mut Matrix<T>,
+    x: usize,
+    y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Translates a position inside a view of `width`×`height` at `(x, y)` into
+/// an index of the underlying `matrix_width` wide matrix
+fn view_index<P>(
+    position: (P, P),
+    origin: (usize, usize),
+    size: (usize, usize),
+    matrix_width: usize,
+) -> Option<usize>
+where
+    P: TryInto<i32>,
+{
+    let x = position.0.try_into().ok().unwrap();
+    let y = position.1.try_into().ok().unwrap();
+    if x < 0 || x as usize >= size.0 || y < 0 || y as usize >= size.1 {
+        return None;
+    }
+
+    Some(origin.0 + x as usize + (origin.1 + y as usize) * matrix_width)
+}
+
+impl<T> Matrix<T> {
+    /// Row `y`, `None` when it is outside of the matrix
+    #[allow(dead_code)]
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.data[y * self.width..(y + 1) * self.width])
+    }
+
+    #[allow(dead_code)]
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.data[y * self.width..(y + 1) * self.width])
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width);
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    #[allow(dead_code)]
+    pub fn column_mut(&mut self, x: usize) -> impl Iterator<Item = &mut T> {
+        assert!(x < self.width);
+        let width = self.width;
+        self.data.iter_mut().skip(x).step_by(width)
+    }
+
+    #[allow(dead_code)]
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.width.max(1))
+    }
+
+    #[allow(dead_code)]
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    #[allow(dead_code)]
+    pub fn view(&self, x: usize, y: usize, width: usize, height: usize) -> SubMatrix<'_, T> {
+        assert!(x + width <= self.width && y + height <= self.height);
+
+        SubMatrix {
+            matrix: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn view_mut(
+        &mut self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+    ) -> SubMatrixMut<'_, T> {
+        assert!(x + width <= self.width && y + height <= self.height);
+
+        SubMatrixMut {
+            matrix: self,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Iterates over every `width`×`height` window, moving left to right, then top to bottom.
+    /// There are no empty windows, so a zero width or height yields nothing.
+    #[allow(dead_code)]
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = SubMatrix<'_, T>> {
+        let (columns, rows) = match (width, height) {
+            (0, _) | (_, 0) => (0, 0),
+            _ => (
+                (self.width + 1).saturating_sub(width),
+                (self.height + 1).saturating_sub(height),
+            ),
+        };
+
+        (0..columns * rows)
+            .map(move |index| self.view(index % columns, index / columns, width, height))
+    }
+}
+
+impl<'a, T> SubMatrix<'a, T> {
+    /// Position of the top left corner in the underlying matrix
+    #[allow(dead_code)]
+    pub fn origin(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    #[allow(dead_code)]
+    pub fn get<P>(&self, x: P, y: P) -> Option<&'a T>
+    where
+        P: TryInto<i32>,
+    {
+        view_index(
+            (x, y),
+            (self.x, self.y),
+            (self.width, self.height),
+            self.matrix.width,
+        )
+        .map(|index| &self.matrix.data[index])
+    }
+
+    /// Row `y` of the view, `None` when it is outside of the view
+    #[allow(dead_code)]
+    pub fn row(&self, y: usize) -> Option<&'a [T]> {
+        if y >= self.height {
+            return None;
+        }
+
+        let start = self.x + (self.y + y) * self.matrix.width;
+        Some(&self.matrix.data[start..start + self.width])
+    }
+
+    #[allow(dead_code)]
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        (0..self.height).filter_map(move |y| self.row(y))
+    }
+
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (&'a T, (usize, usize))> + '_ {
+        self.rows().enumerate().flat_map(move |(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, value)| (value, (x, y)))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn to_matrix(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(self.width, self.height, |x, y| {
+            self.get(x, y).unwrap().clone()
+        })
+    }
+}
+
+impl<'a, T> SubMatrixMut<'a, T> {
+    #[allow(dead_code)]
+    pub fn origin(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    #[allow(dead_code)]
+    pub fn get<P>(&self, x: P, y: P) -> Option<&T>
+    where
+        P: TryInto<i32>,
+    {
+        view_index(
+            (x, y),
+            (self.x, self.y),
+            (self.width, self.height),
+            self.matrix.width,
+        )
+        .map(|index| &self.matrix.data[index])
+    }
+
+    #[allow(dead_code)]
+    pub fn get_mut<P>(&mut self, x: P, y: P) -> Option<&mut T>
+    where
+        P: TryInto<i32>,
+    {
+        view_index(
+            (x, y),
+            (self.x, self.y),
+            (self.width, self.height),
+            self.matrix.width,
+        )
+        .map(|index| &mut self.matrix.data[index])
+    }
+
+    #[allow(dead_code)]
+    pub fn set<P>(&mut self, x: P, y: P, value: T)
+    where
+        P: TryInto<i32>,
+    {
+        if let Some(cell) = self.get_mut(x, y) {
+            *cell = value;
+        }
+    }
+
+    /// Row `y` of the view, `None` when it is outside of the view
+    #[allow(dead_code)]
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        if y >= self.height {
+            return None;
+        }
+
+        let start = self.x + (self.y + y) * self.matrix.width;
+        Some(&mut self.matrix.data[start..start + self.width])
+    }
+
+    #[allow(dead_code)]
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for y in 0..self.height {
+            self.row_mut(y).unwrap().fill(value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::matrix::Matrix;
+
+    #[test]
+    fn matrix_rows_and_columns() {
+        let matrix = Matrix::<u8>::from("123\n456").unwrap();
+
+        assert_eq!(matrix.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(matrix.row(2), None);
+        assert_eq!(matrix.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(matrix.rows().count(), 2);
+
+        let sums = matrix
+            .columns()
+            .map(|column| column.sum::<u8>())
+            .collect::<Vec<_>>();
+        assert_eq!(sums, vec![5, 7, 9]);
+    }
+
+    #[test]
+    fn matrix_sub_matrix() {
+        let mut matrix = Matrix::<u8>::from("1234\n5678\n9012").unwrap();
+
+        let view = matrix.view(1, 1, 2, 2);
+        assert_eq!(view.get(0, 0), Some(&6));
+        assert_eq!(view.get(2, 0), None);
+        assert_eq!(view.rows().collect::<Vec<_>>(), vec![&[6, 7], &[0, 1]]);
+        assert_eq!(view.row(1), Some(&[0, 1][..]));
+        assert_eq!(view.row(2), None);
+        assert_eq!(view.to_matrix().to_string(), "67\n01\n");
+
+        let mut view = matrix.view_mut(2, 0, 2, 3);
+        view.set(1, 2, 0);
+        view.row_mut(0).unwrap().fill(9);
+        assert!(view.row_mut(3).is_none());
+        assert_eq!(matrix.to_string(), "1299\n5678\n9010\n");
+
+        matrix.view_mut(0, 1, 4, 1).fill(0);
+        assert_eq!(matrix.to_string(), "1299\n0000\n9010\n");
+    }
+
+    #[test]
+    fn matrix_windows() {
+        let matrix = Matrix::<u8>::from("123\n456\n789").unwrap();
+
+        let windows = matrix
+            .windows(2, 2)
+            .map(|window| (window.origin(), window.iter().map(|(&v, _)| v).sum::<u8>()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            windows,
+            vec![((0, 0), 12), ((1, 0), 16), ((0, 1), 24), ((1, 1), 28)]
+        );
+
+        assert_eq!(matrix.windows(3, 3).count(), 1);
+        assert_eq!(matrix.windows(4, 1).count(), 0);
+        assert_eq!(matrix.windows(0, 0).count(), 0);
+        assert_eq!(matrix.windows(2, 0).count(), 0);
+    }
+}
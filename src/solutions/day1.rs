@@ -1,49 +1,243 @@
-use crate::helpers;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Options, Problem, Result, Solution};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 use std::fmt::Display;
+use std::io::BufRead;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    /// Measurements summed in each window of the analysis, 1 when not set
+    window: Option<usize>,
+    /// Largest jumps listed by the analysis, 3 when not set
+    jumps: Option<usize>,
+}
+
+/// Consecutive measurements, `start` is the index of the first one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Run {
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Change of depth between the measurement at `position` and the previous one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Jump {
+    pub position: usize,
+    pub change: i64,
+}
+
+/// Summary of a sonar sweep, positions are 0-based indexes of the measurements
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepthReport {
+    pub measurements: usize,
+    /// Number of times the sum of a window is larger than the sum of the previous window
+    pub increases: usize,
+    /// Smallest depth and the position of its first occurrence
+    pub min: Option<(u32, usize)>,
+    /// Largest depth and the position of its first occurrence
+    pub max: Option<(u32, usize)>,
+    pub longest_increasing: Run,
+    pub longest_decreasing: Run,
+    /// Largest changes between consecutive measurements, largest first
+    pub largest_jumps: Vec<Jump>,
+}
+
+impl Display for DepthReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let depth = |depth: Option<(u32, usize)>| match depth {
+            Some((depth, position)) => format!("{} at {}", depth, position),
+            None => "-".to_string(),
+        };
+        let run = |run: Run| format!("{} from {}", run.length, run.start);
+        let jumps = self
+            .largest_jumps
+            .iter()
+            .map(|jump| format!("{:+} at {}", jump.change, jump.position))
+            .collect::<Vec<_>>();
+
+        writeln!(f, "measurements: {}", self.measurements)?;
+        writeln!(f, "increases: {}", self.increases)?;
+        writeln!(f, "min: {}", depth(self.min))?;
+        writeln!(f, "max: {}", depth(self.max))?;
+        writeln!(f, "longest increasing: {}", run(self.longest_increasing))?;
+        writeln!(f, "longest decreasing: {}", run(self.longest_decreasing))?;
+        writeln!(f, "largest jumps: {}", jumps.join(", "))
+    }
+}
+
+/// Analyzes depths one by one, keeping only the last `window` measurements and
+/// the `jumps` largest jumps in memory
+pub struct DepthAnalyzer {
+    window: usize,
+    jumps: usize,
+    recent: VecDeque<u32>,
+    increasing: Run,
+    decreasing: Run,
+    largest_jumps: BinaryHeap<Reverse<(u64, Reverse<usize>, i64)>>,
+    report: DepthReport,
+}
+
+impl DepthAnalyzer {
+    pub fn new(window: usize, jumps: usize) -> DepthAnalyzer {
+        assert!(window > 0, "window must not be empty");
+
+        Self {
+            window,
+            jumps,
+            recent: VecDeque::with_capacity(window + 1),
+            increasing: Run::default(),
+            decreasing: Run::default(),
+            largest_jumps: BinaryHeap::with_capacity(jumps + 1),
+            report: DepthReport::default(),
+        }
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        let position = self.report.measurements;
+        self.report.measurements += 1;
+
+        if self.report.min.is_none_or(|(min, _)| depth < min) {
+            self.report.min = Some((depth, position));
+        }
+        if self.report.max.is_none_or(|(max, _)| depth > max) {
+            self.report.max = Some((depth, position));
+        }
+
+        let previous = self.recent.back().copied();
+        self.increasing = match previous {
+            Some(previous) if depth > previous => Run {
+                length: self.increasing.length + 1,
+                ..self.increasing
+            },
+            _ => Run {
+                start: position,
+                length: 1,
+            },
+        };
+        self.decreasing = match previous {
+            Some(previous) if depth < previous => Run {
+                length: self.decreasing.length + 1,
+                ..self.decreasing
+            },
+            _ => Run {
+                start: position,
+                length: 1,
+            },
+        };
+        if self.increasing.length > self.report.longest_increasing.length {
+            self.report.longest_increasing = self.increasing;
+        }
+        if self.decreasing.length > self.report.longest_decreasing.length {
+            self.report.longest_decreasing = self.decreasing;
+        }
+
+        if let Some(previous) = previous {
+            let change = depth as i64 - previous as i64;
+            self.largest_jumps
+                .push(Reverse((change.unsigned_abs(), Reverse(position), change)));
+            if self.largest_jumps.len() > self.jumps {
+                self.largest_jumps.pop();
+            }
+        }
+
+        // Both windows share every measurement but the first of the previous window
+        // and the last one of the current window
+        self.recent.push_back(depth);
+        if self.recent.len() > self.window && self.recent.pop_front().unwrap() < depth {
+            self.report.increases += 1;
+        }
+    }
+
+    pub fn finish(self) -> DepthReport {
+        let mut report = self.report;
+        report.largest_jumps = self
+            .largest_jumps
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((_, Reverse(position), change))| Jump { position, change })
+            .collect();
+
+        report
+    }
+}
 
 impl DaySolution {
-    fn solve(&self, arr: &[u32]) -> u32 {
-        arr.iter()
-            .fold((0, None), |(acc, prev), curr| match prev {
-                Some(prev) if prev < curr => (acc + 1, Some(curr)),
-                _ => (acc, Some(curr)),
-            })
-            .0
+    /// Streams the depths from `reader`, one per line, through a `DepthAnalyzer`
+    pub fn analyze<R: BufRead>(
+        &self,
+        mut reader: R,
+        window: usize,
+        jumps: usize,
+    ) -> Result<DepthReport> {
+        let mut analyzer = DepthAnalyzer::new(window, jumps);
+        let mut line = String::new();
+
+        for number in 1.. {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+
+            let depth = line.trim();
+            if depth.is_empty() {
+                continue;
+            }
+
+            let depth = depth
+                .parse()
+                .map_err(|_| Problem::new(number, format!("invalid depth {:?}", depth)))?;
+            analyzer.push(depth);
+        }
+
+        Ok(analyzer.finish())
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let nums = helpers::parse_lines(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let report = DaySolution::analyze(self, input.unwrap_or_default().as_bytes(), 1, 0)?;
 
-        Ok(Box::new(self.solve(&nums)))
+        Ok(Box::new(report.increases))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let sums = helpers::parse_lines(input)
-            .windows(3)
-            .map(|w| w.iter().sum())
-            .collect::<Vec<_>>();
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let report = DaySolution::analyze(self, input.unwrap_or_default().as_bytes(), 3, 0)?;
+
+        Ok(Box::new(report.increases))
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.window = options.window;
+        self.jumps = options.jumps;
+        Ok(())
+    }
+
+    fn analyze(&mut self, reader: &mut dyn BufRead) -> Result<Option<String>> {
+        let (window, jumps) = (self.window.unwrap_or(1), self.jumps.unwrap_or(3));
+        if window == 0 {
+            return Err("the window must not be empty".into());
+        }
+
+        let report = DaySolution::analyze(self, reader, window, jumps)?;
+
+        Ok(Some(report.to_string()))
+    }
 
-        Ok(Box::new(self.solve(&sums)))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(DaySolution::analyze(self, input.as_bytes(), 1, 0))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day1::DaySolution;
+    use crate::day1::{DaySolution, Jump, Run};
+    use crate::solutions::{Options, Problem};
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day1_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("7", result.to_string())
     }
@@ -51,10 +245,88 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day1_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("5", result.to_string())
     }
+
+    #[test]
+    fn analyze() {
+        let input = include_str!("../../inputs/day1_demo.txt");
+        let report = DaySolution::default()
+            .analyze(input.as_bytes(), 2, 3)
+            .unwrap();
+
+        assert_eq!(report.measurements, 10);
+        assert_eq!(report.increases, 5);
+        assert_eq!(report.min, Some((199, 0)));
+        assert_eq!(report.max, Some((269, 7)));
+        assert_eq!(
+            report.longest_increasing,
+            Run {
+                start: 0,
+                length: 4
+            }
+        );
+        assert_eq!(
+            report.longest_decreasing,
+            Run {
+                start: 3,
+                length: 2
+            }
+        );
+        assert_eq!(
+            report.largest_jumps,
+            vec![
+                Jump {
+                    position: 6,
+                    change: 33
+                },
+                Jump {
+                    position: 7,
+                    change: 29
+                },
+                Jump {
+                    position: 4,
+                    change: -10
+                },
+            ]
+        );
+
+        // Windows larger than the input never compare
+        let report = DaySolution::default()
+            .analyze(input.as_bytes(), 20, 0)
+            .unwrap();
+        assert_eq!(report.increases, 0);
+
+        let error = DaySolution::default()
+            .analyze("1\n2\nx\n".as_bytes(), 1, 0)
+            .unwrap_err();
+        assert_eq!(error.to_string(), "line 3: invalid depth \"x\"");
+        assert_eq!(
+            DaySolution::default().lint("1\n2\nx\n"),
+            vec![Problem::new(3, "invalid depth \"x\"")]
+        );
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            window: Some(3),
+            jumps: Some(1),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let report = Solution::analyze(&mut solution, &mut input.as_bytes()).unwrap();
+        assert_eq!(
+            report.unwrap().lines().collect::<Vec<_>>(),
+            vec![
+                "measurements: 10",
+                "increases: 5",
+                "min: 199 at 0",
+                "max: 269 at 7",
+                "longest increasing: 4 from 0",
+                "longest decreasing: 2 from 3",
+                "largest jumps: +33 at 6",
+            ]
+        );
+    }
 }
@@ -1,6 +1,5 @@
 use crate::day10::Route::{Incomplete, Valid};
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 use std::collections::VecDeque;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -16,11 +15,15 @@ enum Route {
 }
 
 impl FromStr for Route {
-    type Err = ();
+    type Err = String;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let mut stack: VecDeque<char> = VecDeque::new();
 
+        if let Some(ch) = s.chars().find(|ch| !"()[]{}<>".contains(*ch)) {
+            return Err(format!("invalid character {:?}", ch));
+        }
+
         for ch in s.chars() {
             if ch == '(' || ch == '[' || ch == '<' || ch == '{' {
                 stack.push_front(ch);
@@ -47,10 +50,17 @@ impl FromStr for Route {
     }
 }
 
+impl DaySolution {
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Route>> {
+        solutions::parse_lines(input.unwrap_or_default(), |line| line.trim().parse())
+    }
+}
+
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let result = parse_lines::<Route>(input)
-            .iter()
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let result = self
+            .parse(input)?
+            .into_iter()
             .map(|route| match route {
                 Route::Invalid(ch) => match ch {
                     ')' => 3,
@@ -66,9 +76,10 @@ impl Solution for DaySolution {
         Ok(Box::new(result))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let scores = parse_lines::<Route>(input)
-            .iter()
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let scores = self
+            .parse(input)?
+            .into_iter()
             .filter_map(|route| match route {
                 Route::Incomplete(stack) => Some(stack),
                 _ => None,
@@ -90,6 +101,10 @@ impl Solution for DaySolution {
 
         Ok(Box::new(scores[scores.len() / 2]))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
@@ -100,9 +115,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day10_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("26397", result.to_string())
     }
@@ -110,9 +123,7 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day10_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("288957", result.to_string())
     }
@@ -1,76 +1,129 @@
-use std::collections::{HashSet, VecDeque};
+use itertools::Itertools;
 use std::fmt::Display;
 
-use crate::matrix::{Matrix, MATRIX_NEIGHBOURS_8};
-use crate::solutions::{Result, Solution};
+use crate::matrix::automaton::{Automaton, Border, Neighbourhood};
+use crate::matrix::Matrix;
+use crate::solutions::{self, Options, Problem, Result, Solution};
+
+/// Energy level and whether the octopus already flashed during the step
+type Octopus = (u32, bool);
 
 #[derive(Default)]
-pub struct DaySolution {}
+pub struct DaySolution {
+    /// Steps already made by the octopuses of the input
+    start_step: usize,
+}
 
 impl DaySolution {
-    fn step(&self, matrix: &mut Matrix<i32>) -> usize {
-        let mut queue: VecDeque<(i32, i32)> = VecDeque::new();
-
-        queue.extend(matrix.iter_mut().filter_map(|(energy, (x, y))| {
-            *energy += 1;
-            (*energy > 9).then(|| (x as i32, y as i32))
-        }));
-
-        let mut flashed = HashSet::new();
-        while let Some((x, y)) = queue.pop_front() {
-            if flashed.contains(&(x, y)) {
-                continue;
-            }
-
-            flashed.insert((x, y));
-            queue.extend(MATRIX_NEIGHBOURS_8.iter().filter_map(|(dx, dy)| {
-                matrix.get_mut(x + dx, y + dy).and_then(|energy| {
-                    *energy += 1;
-                    (*energy > 9).then(|| (x + dx, y + dy))
-                })
-            }));
-        }
+    fn parse(&self, input: Option<&str>) -> Result<Automaton<Octopus>> {
+        let matrix = Matrix::<u32>::parse(input.unwrap_or_default())?;
+
+        // Nothing outside of the grid can flash
+        Ok(Automaton::new(
+            matrix.map(|&energy| (energy, false)),
+            Border::Fixed((0, true)),
+        )?)
+    }
+
+    fn flash(neighbourhood: &Neighbourhood<Octopus>) -> Octopus {
+        let (energy, flashed) = *neighbourhood.cell();
+        let flashing = neighbourhood
+            .neighbours8()
+            .filter(|&&(energy, flashed)| energy > 9 && !flashed)
+            .count() as u32;
+
+        (energy + flashing, flashed || energy > 9)
+    }
 
-        matrix.iter_mut().for_each(|(energy, _)| {
-            if *energy > 9 {
-                *energy = 0;
-            }
+    fn step(&self, octopuses: &mut Automaton<Octopus>) -> usize {
+        octopuses.step(|n| (n.cell().0 + 1, false));
+        octopuses.run_until_stable(Self::flash);
+
+        let flashed = octopuses.grid.iter().filter(|(&(_, f), _)| f).count();
+        octopuses.step(|n| match *n.cell() {
+            (_, true) => (0, false),
+            octopus => octopus,
         });
 
-        flashed.len()
+        flashed
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let mut matrix = Matrix::<i32>::from(&input.unwrap()).unwrap();
-        let result = (0..100).map(|_| self.step(&mut matrix)).sum::<usize>();
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        if self.start_step >= 100 {
+            let message = format!("step 100 is before the snapshot step {}", self.start_step);
+            return Err(message.into());
+        }
+
+        let mut octopuses = self.parse(input)?;
+        let steps = 100 - self.start_step;
+        let result = (0..steps).map(|_| self.step(&mut octopuses)).sum::<usize>();
 
-        Ok(Box::new(result))
+        // The flashes before the snapshot step are unknown, only the later ones are counted
+        match self.start_step {
+            0 => Ok(Box::new(result)),
+            _ => Ok(Box::new(format!("at least {}", result))),
+        }
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let mut matrix = Matrix::<i32>::from(&input.unwrap()).unwrap();
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let mut octopuses = self.parse(input)?;
+
+        // Octopuses that flash together keep the same energy, whichever step they synchronised at
+        let together = octopuses
+            .grid
+            .iter()
+            .map(|(&(energy, _), _)| energy)
+            .all_equal();
+        if self.start_step > 0 && together {
+            let message = format!(
+                "the octopuses already flash together by step {}",
+                self.start_step
+            );
+            return Err(message.into());
+        }
 
         let result = std::iter::repeat(1)
-            .take_while(|_| self.step(&mut matrix) != matrix.size())
+            .take_while(|_| self.step(&mut octopuses) != octopuses.grid.size())
             .sum::<usize>();
 
-        Ok(Box::new(result + 1))
+        Ok(Box::new(self.start_step + result + 1))
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.start_step = options.start_step;
+        Ok(())
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
+
+    fn snapshot(&mut self, input: Option<&str>, steps: usize) -> Result<Option<Matrix<char>>> {
+        let mut octopuses = self.parse(input)?;
+        (0..steps).for_each(|_| {
+            self.step(&mut octopuses);
+        });
+
+        Ok(Some(
+            octopuses
+                .grid
+                .map(|&(energy, _)| char::from_digit(energy, 10).unwrap()),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day11::DaySolution;
+    use crate::solutions::Options;
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day11_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("1656", result.to_string())
     }
@@ -78,10 +131,47 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day11_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("195", result.to_string())
     }
+
+    #[test]
+    fn resume() {
+        let input = include_str!("../../inputs/day11_demo.txt");
+        let grid = DaySolution::default().snapshot(Some(input), 50).unwrap();
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            start_step: 50,
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let grid = grid.unwrap().to_string();
+        assert_eq!("195", solution.part_2(Some(&grid)).unwrap().to_string());
+
+        let mut octopuses = solution.parse(Some(input)).unwrap();
+        let before = (0..50)
+            .map(|_| solution.step(&mut octopuses))
+            .sum::<usize>();
+        assert_eq!(
+            solution.part_1(Some(&grid)).unwrap().to_string(),
+            format!("at least {}", 1656 - before)
+        );
+
+        for start_step in [195, 200] {
+            let grid = DaySolution::default()
+                .snapshot(Some(input), start_step)
+                .unwrap()
+                .unwrap()
+                .to_string();
+            let options = Options {
+                start_step,
+                ..Options::default()
+            };
+            solution.configure(&options).unwrap();
+            assert!(solution.part_2(Some(&grid)).is_err());
+            assert!(solution.part_1(Some(&grid)).is_err());
+        }
+    }
 }
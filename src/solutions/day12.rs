@@ -1,8 +1,7 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Display;
 
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -14,13 +13,20 @@ impl DaySolution {
         (name.to_string(), name.chars().all(|ch| ch.is_lowercase()))
     }
 
-    fn parse(&self, input: Option<String>) -> HashMap<Node, Vec<Node>> {
-        let lines = parse_lines::<String>(input);
+    fn parse(&self, input: Option<&str>) -> Result<HashMap<Node, Vec<Node>>> {
+        let edges = solutions::parse_lines(input.unwrap_or_default(), |line| {
+            line.trim()
+                .split_once('-')
+                .filter(|(from, to)| {
+                    [from, to].iter().all(|name| {
+                        !name.is_empty() && name.chars().all(|ch| ch.is_ascii_alphabetic())
+                    })
+                })
+                .ok_or_else(|| format!("expected a connection `cave-cave`, found {:?}", line))
+        })?;
         let mut graph = HashMap::<_, Vec<_>>::new();
 
-        for line in lines {
-            let (from, to) = line.split_once('-').unwrap();
-
+        for (from, to) in edges {
             let from = self.node_build(from);
             let to = self.node_build(to);
 
@@ -31,7 +37,11 @@ impl DaySolution {
             graph.entry(to).or_insert_with(Vec::new).push(from);
         }
 
-        graph
+        if !graph.contains_key(&self.node_build("start")) {
+            return Err(Problem::input("no connection to the start cave").into());
+        }
+
+        Ok(graph)
     }
 
     fn solve(&self, graph: &HashMap<Node, Vec<Node>>, count_twice: bool) -> usize {
@@ -71,15 +81,19 @@ impl DaySolution {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let graph = self.parse(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let graph = self.parse(input)?;
         Ok(Box::new(self.solve(&graph, false)))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let graph = self.parse(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let graph = self.parse(input)?;
         Ok(Box::new(self.solve(&graph, true)))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
@@ -90,16 +104,12 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day12_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("10", result.to_string());
 
         let input = include_str!("../../inputs/day12.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("4413", result.to_string());
     }
@@ -107,16 +117,12 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day12_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("36", result.to_string());
 
         let input = include_str!("../../inputs/day12.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("118803", result.to_string());
     }
@@ -1,8 +1,10 @@
 use std::fmt::Display;
-use std::ops::BitOr;
 
+use crate::helpers;
+use crate::matrix::bits::BitMatrix;
+use crate::matrix::image::{Rgb, BLACK, WHITE};
 use crate::matrix::Matrix;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -13,114 +15,111 @@ enum Flip {
     Vertical(usize),
 }
 
-trait Flippable<T> {
-    fn flip(&self, axis: &Flip) -> Matrix<T>;
+trait Flippable {
+    fn flip(&self, axis: &Flip) -> Self;
 }
 
-impl<T> Flippable<T> for Matrix<T>
-where
-    T: Default + Copy + BitOr<Output = T>,
-{
-    fn flip(&self, axis: &Flip) -> Matrix<T> {
-        let (width, height) = match axis {
-            Flip::Vertical(value) => (self.width, *value),
-            Flip::Horizontal(value) => (*value, self.height),
-        };
-
-        let mut new_matrix: Matrix<T> = Matrix::new(width, height);
-
-        for y in 0..height {
-            for x in 0..width {
-                let (xx, yy) = match axis {
-                    Flip::Horizontal(_) => (self.width - x - 1, y),
-                    Flip::Vertical(_) => (x, self.height - y - 1),
-                };
-
-                let value1 = *self.get(x, y).unwrap();
-                let value2 = *self.get(xx, yy).unwrap();
-
-                new_matrix.set(x, y, value1 | value2);
-            }
+impl Flippable for BitMatrix {
+    fn flip(&self, axis: &Flip) -> Self {
+        match axis {
+            Flip::Horizontal(value) => self.fold_left(*value),
+            Flip::Vertical(value) => self.fold_up(*value),
         }
-
-        new_matrix
     }
 }
 
 impl DaySolution {
-    fn parse(&self, input: Option<String>) -> (Matrix<u8>, Vec<Flip>) {
-        let input = input.unwrap();
-
-        let mut width = 0;
-        let mut height = 0;
-
-        let points = input
-            .lines()
-            .take_while(|&line| !line.is_empty())
-            .map(|line| {
-                let (x, y) = line.split_once(',').unwrap();
-                let (x, y) = (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap());
-
-                if x > width {
-                    width = x;
-                }
-
-                if y > height {
-                    height = y;
-                }
+    /// Dots as `x,y` lines, then the fold instructions after an empty line
+    fn parse(&self, input: Option<&str>) -> Result<(BitMatrix, Vec<Flip>)> {
+        let input = input.unwrap_or_default();
+        let blocks = helpers::blocks(input);
+        let mut problems = vec![];
+        fn numbered<'a>((start, lines): &(usize, Vec<&'a str>)) -> Vec<(usize, &'a str)> {
+            (*start..).zip(lines.iter().copied()).collect()
+        }
 
-                (x, y)
+        let points = blocks.first().map(numbered).map_or(vec![], |lines| {
+            solutions::parse_each(lines, &mut problems, |line| {
+                line.split_once(',')
+                    .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+                    .ok_or_else(|| format!("expected a point `x,y`, found {:?}", line))
             })
-            .collect::<Vec<_>>();
+        });
 
-        let flips = input
-            .lines()
-            .skip_while(|&line| !line.is_empty())
-            .skip(1)
-            .map(|line| {
-                let (_, value) = line.split_once('=').unwrap();
-                let value = value.parse().unwrap();
-                match line {
-                    _ if line.starts_with("fold along y=") => Flip::Vertical(value),
-                    _ => Flip::Horizontal(value),
+        let flips = match blocks.get(1).map(numbered) {
+            Some(lines) => solutions::parse_each(lines, &mut problems, |line| {
+                let fold = line
+                    .strip_prefix("fold along ")
+                    .and_then(|fold| fold.split_once('='))
+                    .and_then(|(axis, value)| Some((axis, value.parse::<usize>().ok()?)));
+                match fold {
+                    Some(("x", value)) => Ok(Flip::Horizontal(value)),
+                    Some(("y", value)) => Ok(Flip::Vertical(value)),
+                    _ => Err(format!(
+                        "expected `fold along x=<n>` or `fold along y=<n>`, found {:?}",
+                        line
+                    )),
                 }
-            })
-            .collect::<Vec<_>>();
+            }),
+            None => {
+                let line = input.lines().count() + 1;
+                problems.push(Problem::new(line, "missing the fold instructions"));
+                vec![]
+            }
+        };
 
-        let mut matrix = Matrix::new(width + 1, height + 1);
-        points.iter().for_each(|(x, y)| {
-            matrix.set(*x, *y, 1);
-        });
+        if let Some((start, _)) = blocks.get(2) {
+            problems.push(Problem::new(
+                *start,
+                "unexpected lines after the fold instructions",
+            ));
+        }
 
-        (matrix, flips)
+        let width = points.iter().map(|&(x, _)| x).max().unwrap_or(0);
+        let height = points.iter().map(|&(_, y)| y).max().unwrap_or(0);
+        let mut matrix = BitMatrix::new(width + 1, height + 1);
+        points.iter().for_each(|&(x, y)| matrix.set(x, y, true));
+
+        Problems::result(problems, (matrix, flips))
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (mut matrix, flips) = self.parse(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (mut matrix, flips) = self.parse(input)?;
 
         flips.iter().take(1).for_each(|flip| {
             matrix = matrix.flip(flip);
         });
 
-        let count = matrix.iter().filter(|(&v, _)| v == 1).count();
-        Ok(Box::new(count))
+        Ok(Box::new(matrix.count_ones()))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (mut matrix, flips) = self.parse(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (mut matrix, flips) = self.parse(input)?;
 
         flips.iter().for_each(|flip| {
             matrix = matrix.flip(flip);
         });
 
-        let result = matrix.render_to_string(|value| match value {
-            Some(&1) => "#".to_string(),
-            _ => ".".to_string(),
-        });
+        Ok(Box::new(format!("\n{}", matrix.to_string().trim_end())))
+    }
+
+    fn render(&mut self, input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        let (matrix, flips) = self.parse(input)?;
+        let matrix = flips.iter().fold(matrix, |matrix, flip| matrix.flip(flip));
+
+        Ok(Some(matrix.to_matrix().map(|&dot| {
+            if dot {
+                BLACK
+            } else {
+                WHITE
+            }
+        })))
+    }
 
-        Ok(Box::new(format!("\n{}", result)))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
     }
 }
 
@@ -132,16 +131,12 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day13_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("17", result.to_string());
 
         let input = include_str!("../../inputs/day13.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("664", result.to_string());
     }
@@ -149,9 +144,7 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day13_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!(
             "
@@ -166,9 +159,7 @@ mod tests {
         );
 
         let input = include_str!("../../inputs/day13.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!(
             "
@@ -181,4 +172,24 @@ mod tests {
             result.to_string()
         );
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day13_demo.txt");
+        assert_eq!(DaySolution.lint(input), vec![]);
+
+        let input = "6,10\n0;14\n\nfold along y=7\nfold along z=5\nfold y=3";
+        let lines = DaySolution
+            .lint(input)
+            .iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![2, 5, 6]);
+
+        let problems = DaySolution.lint("1,1\n2,2");
+        assert_eq!(
+            problems[0].to_string(),
+            "line 3: missing the fold instructions"
+        );
+    }
 }
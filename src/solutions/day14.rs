@@ -3,35 +3,72 @@ use std::fmt::Display;
 
 use itertools::{Itertools, MinMaxResult};
 
-use crate::solutions::{Result, Solution};
+use crate::helpers;
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
 
-impl DaySolution {
-    fn solve(&self, input: Option<String>, steps: usize) -> usize {
-        let lines = input.unwrap().lines().map(String::from).collect::<Vec<_>>();
+/// Letter inserted between the letters of a pair, along with the two pairs it makes
+type Rules = HashMap<String, (String, Vec<String>)>;
 
-        let polymer = lines.first().unwrap().to_string();
-        let rules: HashMap<String, (String, Vec<String>)> =
-            HashMap::from_iter(lines.iter().skip(2).map(|line| {
-                let (pair, letter) = line.split_once(" -> ").unwrap();
+impl DaySolution {
+    /// Polymer template on the first line, then the pair insertion rules after an empty line
+    fn parse(&self, input: Option<&str>) -> Result<(String, Rules)> {
+        let input = input.unwrap_or_default();
+        let blocks = helpers::blocks(input);
+        let mut problems = vec![];
+
+        let polymer = match blocks.first() {
+            Some((start, lines)) if lines.len() == 1 => {
+                if !lines[0].chars().all(|ch| ch.is_ascii_uppercase()) {
+                    let message = format!("invalid polymer template {:?}", lines[0]);
+                    problems.push(Problem::new(*start, message));
+                }
+                lines[0].to_string()
+            }
+            Some((start, _)) => {
+                problems.push(Problem::new(*start, "expected a single template line"));
+                String::new()
+            }
+            None => return Err(Problem::input("empty input").into()),
+        };
+
+        let rules = blocks.get(1).map_or(vec![], |(start, lines)| {
+            solutions::parse_each((*start..).zip(lines.clone()), &mut problems, |line| {
+                let (pair, letter) = line
+                    .split_once(" -> ")
+                    .filter(|(pair, letter)| {
+                        pair.len() == 2
+                            && letter.len() == 1
+                            && pair
+                                .chars()
+                                .chain(letter.chars())
+                                .all(|ch| ch.is_ascii_uppercase())
+                    })
+                    .ok_or_else(|| format!("expected a rule `AB -> C`, found {:?}", line))?;
                 let (first, last) = pair.split_at(1);
 
                 let first = format!("{}{}", first, letter);
                 let last = format!("{}{}", letter, last);
 
-                (pair.to_string(), (letter.to_string(), vec![first, last]))
-            }));
+                Ok((pair.to_string(), (letter.to_string(), vec![first, last])))
+            })
+        });
+
+        Problems::result(problems, (polymer, HashMap::from_iter(rules)))
+    }
+
+    fn solve(&self, input: Option<&str>, steps: usize) -> Result<usize> {
+        let (polymer, rules) = self.parse(input)?;
 
         let mut polymer_pairs: HashMap<String, usize> = HashMap::new();
         let mut letters: HashMap<String, usize> = HashMap::new();
 
         polymer
             .chars()
-            .map(|ch| {
+            .inspect(|ch| {
                 *letters.entry(ch.to_string()).or_insert(0) += 1;
-                ch
             })
             .collect::<Vec<_>>()
             .windows(2)
@@ -42,32 +79,40 @@ impl DaySolution {
 
         for _ in 0..steps {
             let mut tmp = HashMap::<String, usize>::new();
-            polymer_pairs.iter().for_each(|(pair, count)| {
-                let (letter, new_pairs) = rules.get(pair).unwrap();
-                *letters.entry(letter.clone()).or_insert(0) += count;
-                new_pairs.iter().for_each(|pair| {
-                    *tmp.entry(pair.clone()).or_insert(0) += count;
+            polymer_pairs
+                .iter()
+                .for_each(|(pair, count)| match rules.get(pair) {
+                    Some((letter, new_pairs)) => {
+                        *letters.entry(letter.clone()).or_insert(0) += count;
+                        new_pairs.iter().for_each(|pair| {
+                            *tmp.entry(pair.clone()).or_insert(0) += count;
+                        });
+                    }
+                    None => *tmp.entry(pair.clone()).or_insert(0) += count,
                 });
-            });
 
             polymer_pairs = tmp;
         }
 
         if let MinMaxResult::MinMax(min, max) = letters.values().minmax() {
-            return max - min;
+            return Ok(max - min);
         }
 
-        0
+        Ok(0)
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 10)))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input, 10)?))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input, 40)?))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 40)))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
     }
 }
 
@@ -79,9 +124,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day14_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("1588", result.to_string())
     }
@@ -89,9 +132,7 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day14_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("2188189693529", result.to_string())
     }
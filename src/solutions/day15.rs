@@ -2,15 +2,27 @@ use std::fmt::Display;
 
 use pathfinding::prelude::dijkstra;
 
+use crate::matrix::image::{gray, Rgb};
 use crate::matrix::Matrix;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
 
 impl DaySolution {
-    fn solve(&self, matrix: &Matrix<usize>) -> Option<usize> {
-        let result = dijkstra(
+    fn parse(&self, input: Option<&str>, tiles: usize) -> Result<Matrix<usize>> {
+        // Risk levels go from 1 to 9
+        let matrix = Matrix::parse_with(input.unwrap_or_default(), |ch| {
+            ch.to_digit(10)
+                .filter(|&risk| risk > 0)
+                .map(|risk| risk as usize)
+        })?;
+
+        Ok(matrix.tile(tiles, tiles, |&value, (i, j)| (value + i + j - 1) % 9 + 1))
+    }
+
+    fn solve(&self, matrix: &Matrix<usize>) -> Option<(Vec<(i32, i32)>, usize)> {
+        dijkstra(
             &(0, 0),
             |&(x, y)| {
                 matrix
@@ -18,35 +30,36 @@ impl DaySolution {
                     .map(|(&risk, pos)| (pos, risk))
             },
             |pos| *pos == (matrix.width as i32 - 1, matrix.height as i32 - 1),
-        );
-
-        result.map(|(_, risk)| risk)
+        )
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let input = input.unwrap();
-        let matrix = Matrix::<usize>::from(&input).unwrap();
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let matrix = self.parse(input, 1)?;
 
-        Ok(Box::new(self.solve(&matrix).unwrap()))
+        Ok(Box::new(self.solve(&matrix).unwrap().1))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let input = input.unwrap();
-        let matrix = Matrix::<usize>::from(&input).unwrap();
-        let mut new_matrix = Matrix::new(matrix.width * 5, matrix.height * 5);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let matrix = self.parse(input, 5)?;
 
-        for (i, j, x, y) in itertools::iproduct!(0..5, 0..5, 0..matrix.width, 0..matrix.height) {
-            let mut value = *matrix.get(x, y).unwrap() + i + j;
-            if value > 9 {
-                value -= 9;
-            }
+        Ok(Box::new(self.solve(&matrix).unwrap().1))
+    }
 
-            new_matrix.set(i * matrix.width + x, j * matrix.height + y, value);
-        }
+    fn render(&mut self, input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        let matrix = self.parse(input, 5)?;
+        let (path, _) = self.solve(&matrix).ok_or("no path found")?;
+
+        let mut image = matrix.map(|&risk| gray(9 - risk, 8));
+        path.into_iter()
+            .for_each(|(x, y)| image.set(x, y, (255, 0, 0)));
+
+        Ok(Some(image))
+    }
 
-        Ok(Box::new(self.solve(&new_matrix).unwrap()))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input), 1))
     }
 }
 
@@ -58,32 +71,24 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day15_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("40", result.to_string());
 
         let input = include_str!("../../inputs/day15.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
         assert_eq!("769", result.to_string());
     }
 
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day15_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("315", result.to_string());
 
         let input = include_str!("../../inputs/day15.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
         assert_eq!("2963", result.to_string());
     }
 }
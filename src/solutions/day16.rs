@@ -1,4 +1,4 @@
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 use nom::branch::alt;
 use nom::bytes::complete::take;
 use nom::multi::{count, many0, many1};
@@ -106,7 +106,7 @@ impl Parser {
             Self::header_item,
             preceded(tag("1"), map_res(take(11usize), Self::from_bin)),
         ))(input)?;
-        let (input, packets) = count(Self::packet, len as usize)(input)?;
+        let (input, packets) = count(Self::packet, len)(input)?;
 
         Ok((
             input,
@@ -122,14 +122,37 @@ impl Parser {
         alt((Parser::packet_literal, Self::packet_op1, Self::packet_op2))(input)
     }
 
-    fn parse(input: &str) -> Option<Packet> {
-        Self::packet(&input.to_bit_string())
-            .map(|(_, packet)| packet)
-            .ok()
+    fn parse(input: &str) -> Result<Packet> {
+        if let Some(ch) = input.chars().find(|ch| !ch.is_ascii_hexdigit()) {
+            return Err(Problem::new(1, format!("invalid hexadecimal digit {:?}", ch)).into());
+        }
+
+        let (_, packet) = Self::packet(&input.to_bit_string())
+            .map_err(|_| Problem::new(1, "the transmission is not a valid packet"))?;
+        packet.check().map_err(|message| Problem::new(1, message))?;
+
+        Ok(packet)
     }
 }
 
 impl Packet {
+    /// Checks the number of sub-packets of every operator
+    fn check(&self) -> core::result::Result<(), String> {
+        let packets = match &self.data {
+            PacketData::Literal(_) => return Ok(()),
+            PacketData::Op(packets) => packets,
+        };
+
+        match (self.r#type, packets.len()) {
+            (0..=3, 0) => Err(format!("operator {} has no sub-packets", self.r#type)),
+            (5..=7, count) if count != 2 => Err(format!(
+                "operator {} needs 2 sub-packets, found {}",
+                self.r#type, count
+            )),
+            _ => packets.iter().try_for_each(Packet::check),
+        }
+    }
+
     fn sum(&self) -> usize {
         match &self.data {
             PacketData::Literal(_) => self.version,
@@ -157,15 +180,19 @@ impl Packet {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let packet = Parser::parse(&input.unwrap()).unwrap();
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let packet = Parser::parse(input.unwrap_or_default().trim())?;
         Ok(Box::new(packet.sum()))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let packet = Parser::parse(&input.unwrap()).unwrap();
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let packet = Parser::parse(input.unwrap_or_default().trim())?;
         Ok(Box::new(packet.value()))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(Parser::parse(input.trim()))
+    }
 }
 
 #[cfg(test)]
@@ -176,13 +203,13 @@ mod tests {
     #[test]
     fn part_1() {
         let result = DaySolution::default()
-            .part_1(Some("8A004A801A8002F478".to_string()))
+            .part_1(Some("8A004A801A8002F478"))
             .unwrap();
 
         assert_eq!("16", result.to_string());
 
         let result = DaySolution::default()
-            .part_1(Some(include_str!("../../inputs/day16.txt").to_string()))
+            .part_1(Some(include_str!("../../inputs/day16.txt")))
             .unwrap();
 
         assert_eq!("951", result.to_string());
@@ -190,14 +217,12 @@ mod tests {
 
     #[test]
     fn part_2() {
-        let result = DaySolution::default()
-            .part_2(Some("C200B40A82".to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some("C200B40A82")).unwrap();
 
         assert_eq!("3", result.to_string());
 
         let result = DaySolution::default()
-            .part_2(Some(include_str!("../../inputs/day16.txt").to_string()))
+            .part_2(Some(include_str!("../../inputs/day16.txt")))
             .unwrap();
 
         assert_eq!("902198718880", result.to_string());
@@ -1,4 +1,4 @@
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 use itertools::iproduct;
 use regex::Regex;
 use std::fmt::Display;
@@ -7,17 +7,29 @@ use std::fmt::Display;
 pub struct DaySolution;
 
 impl DaySolution {
-    fn parse(&self, input: Option<String>) -> (i32, i32, i32, i32) {
-        let re = Regex::new(r"target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)").unwrap();
-        let input = input.unwrap();
-        let caps = re.captures(&input).unwrap();
-
-        (
-            caps.get(1).unwrap().as_str().parse().unwrap(),
-            caps.get(2).unwrap().as_str().parse().unwrap(),
-            caps.get(3).unwrap().as_str().parse().unwrap(),
-            caps.get(4).unwrap().as_str().parse().unwrap(),
-        )
+    fn parse(&self, input: Option<&str>) -> Result<(i32, i32, i32, i32)> {
+        let re = Regex::new(r"^target area: x=(-?\d+)\.\.(-?\d+), y=(-?\d+)\.\.(-?\d+)$").unwrap();
+        let input = input.unwrap_or_default().trim();
+        let caps = re
+            .captures(input)
+            .ok_or_else(|| Problem::new(1, "expected `target area: x=A..B, y=C..D`"))?;
+        let bound = |index: usize| {
+            let text = caps.get(index).unwrap().as_str();
+            text.parse::<i32>()
+                .map_err(|_| Problem::new(1, format!("invalid coordinate {:?}", text)))
+        };
+        let (x_min, x_max, y_min, y_max) = (bound(1)?, bound(2)?, bound(3)?, bound(4)?);
+
+        if x_min > x_max || y_min > y_max {
+            return Err(Problem::new(1, "the target area bounds are reversed").into());
+        }
+        if x_min <= 0 || y_max >= 0 {
+            return Err(
+                Problem::new(1, "the target area must be right of and below the probe").into(),
+            );
+        }
+
+        Ok((x_min, x_max, y_min, y_max))
     }
 }
 
@@ -64,8 +76,8 @@ impl DaySolution {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (x_min, x_max, y_min, y_max) = self.parse(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (x_min, x_max, y_min, y_max) = self.parse(input)?;
 
         let max_height = iproduct!(0..=x_max, y_min..x_max)
             .filter_map(|(dx, dy)| self.hit((dx, dy), x_min, x_max, y_min, y_max))
@@ -75,8 +87,8 @@ impl Solution for DaySolution {
         Ok(Box::new(max_height))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (x_min, x_max, y_min, y_max) = self.parse(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (x_min, x_max, y_min, y_max) = self.parse(input)?;
 
         let count = iproduct!(0..=x_max, y_min..x_max)
             .filter_map(|(dx, dy)| self.hit((dx, dy), x_min, x_max, y_min, y_max))
@@ -84,26 +96,27 @@ impl Solution for DaySolution {
 
         Ok(Box::new(count))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day17::DaySolution;
+    use crate::solutions::Problem;
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day17_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("45", result.to_string());
 
         let input = include_str!("../../inputs/day17.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("5460", result.to_string());
     }
@@ -111,17 +124,28 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day17_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("112", result.to_string());
 
         let input = include_str!("../../inputs/day17.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("3618", result.to_string());
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day17_demo.txt");
+        assert_eq!(vec![] as Vec<Problem>, DaySolution.lint(input));
+
+        let problems = DaySolution.lint("target area: x=30..20, y=-10..-5\n");
+        assert_eq!(
+            "line 1: the target area bounds are reversed",
+            problems[0].to_string()
+        );
+
+        let problems = DaySolution.lint("target x=20..30\n");
+        assert_eq!(1, problems[0].line);
+    }
 }
@@ -4,13 +4,12 @@ use itertools::Itertools;
 use json::JsonValue;
 
 use crate::day18::Item::{Close, Comma, Value};
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Item {
     Open,
     Close,
@@ -28,7 +27,8 @@ impl Item {
 }
 
 trait Snailfish {
-    fn from_string(input: &str) -> Vec<Item>;
+    fn from_string(input: &str) -> core::result::Result<Vec<Item>, String>;
+    fn pair_end(&self, start: usize) -> Option<usize>;
     fn to_string(&self) -> String;
     fn add(&mut self, other: Vec<Item>);
 
@@ -38,16 +38,45 @@ trait Snailfish {
 }
 
 impl Snailfish for Vec<Item> {
-    fn from_string(input: &str) -> Vec<Item> {
-        input
+    fn from_string(input: &str) -> core::result::Result<Vec<Item>, String> {
+        let items = input
+            .trim()
             .chars()
             .map(|ch| match ch {
-                '[' => Item::Open,
-                ']' => Item::Close,
-                ',' => Item::Comma,
-                x => Item::Value(x.to_digit(10).unwrap_or(0) as usize),
+                '[' => Ok(Item::Open),
+                ']' => Ok(Item::Close),
+                ',' => Ok(Item::Comma),
+                x => x
+                    .to_digit(10)
+                    .map(|digit| Item::Value(digit as usize))
+                    .ok_or_else(|| format!("invalid character {:?}", x)),
             })
-            .collect()
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+
+        match items.pair_end(0) {
+            Some(end) if end == items.len() => Ok(items),
+            _ => Err("expected a pair `[a,b]` of numbers or pairs".to_string()),
+        }
+    }
+
+    /// End of the pair starting at `start`, `None` when it isn't well formed
+    fn pair_end(&self, start: usize) -> Option<usize> {
+        let element = |index: usize| match self.get(index)? {
+            Item::Value(_) => Some(index + 1),
+            Item::Open => self.pair_end(index),
+            _ => None,
+        };
+
+        if !matches!(self.get(start)?, Item::Open) {
+            return None;
+        }
+        let comma = element(start + 1)?;
+        if !matches!(self.get(comma)?, Item::Comma) {
+            return None;
+        }
+        let close = element(comma + 1)?;
+
+        matches!(self.get(close)?, Item::Close).then(|| close + 1)
     }
 
     fn to_string(&self) -> String {
@@ -152,6 +181,15 @@ impl Snailfish for Vec<Item> {
 }
 
 impl DaySolution {
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Vec<Item>>> {
+        let numbers = solutions::parse_lines(input.unwrap_or_default(), Vec::from_string)?;
+        if numbers.is_empty() {
+            return Err(Problem::input("no snailfish numbers").into());
+        }
+
+        Ok(numbers)
+    }
+
     fn magnitude(&self, value: JsonValue) -> f64 {
         match value {
             JsonValue::Array(arr) => {
@@ -181,14 +219,14 @@ impl DaySolution {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines::<String>(input);
-        let mut current = Vec::<Item>::from_string(&lines[0]);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let mut numbers = self.parse(input)?.into_iter();
+        let mut current = numbers.next().unwrap();
 
         current.reduce();
 
-        for line in lines.iter().skip(1) {
-            current.add(Vec::from_string(line));
+        for number in numbers {
+            current.add(number);
             current.reduce();
         }
 
@@ -197,48 +235,49 @@ impl Solution for DaySolution {
         Ok(Box::new(self.magnitude(value) as usize))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines::<String>(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let numbers = self.parse(input)?;
 
-        let max = lines
+        let max = numbers
             .iter()
             .permutations(2)
             .map(|pair| {
                 let (a, b) = (pair[0], pair[1]);
-                let mut a = Vec::from_string(a);
+                let mut a = a.clone();
 
-                a.add(Vec::from_string(b));
+                a.add(b.clone());
                 a.reduce();
 
                 let json = json::parse(&a.to_string()).unwrap();
                 self.magnitude(json) as usize
             })
             .max()
-            .unwrap();
+            .ok_or_else(|| Problem::input("at least 2 snailfish numbers are needed"))?;
 
         Ok(Box::new(max))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day18::DaySolution;
+    use crate::solutions::Problem;
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day18_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("4140", result.to_string());
 
         let input = "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]";
 
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("3488", result.to_string());
     }
@@ -246,10 +285,19 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day18_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("3993", result.to_string());
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day18_demo.txt");
+        assert_eq!(vec![] as Vec<Problem>, DaySolution.lint(input));
+
+        let problems = DaySolution.lint("[1,2]\n[1,x]\n[[1,2]\n\n[1,2,3]\n");
+        let lines = problems.iter().map(|p| p.line).collect::<Vec<_>>();
+        assert_eq!(vec![2, 3, 5], lines);
+        assert_eq!("line 2: invalid character 'x'", problems[0].to_string());
+    }
 }
@@ -6,7 +6,8 @@ use std::str::FromStr;
 
 use itertools::{iproduct, Itertools};
 
-use crate::solutions::{Result, Solution};
+use crate::helpers;
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -143,35 +144,56 @@ impl FromStr for Position {
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
         let result = s
             .split(',')
-            .map(|coord| coord.parse::<i32>().unwrap())
-            .collect::<Vec<_>>();
+            .map(|coord| coord.parse::<i32>().map_err(|_| ()))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
 
-        Ok(Position::new(result[0], result[1], result[2]))
+        match result[..] {
+            [x, y, z] => Ok(Position::new(x, y, z)),
+            _ => Err(()),
+        }
     }
 }
 
 impl DaySolution {
-    fn parse(&self, input: String) -> Vec<Scanner> {
-        let mut result = vec![];
-
-        let mut scanner = Scanner::default();
-        for line in input.lines().skip(1) {
-            if line.is_empty() {
-                result.push(scanner.clone());
-                continue;
+    fn parse(&self, input: &str) -> Result<Vec<Scanner>> {
+        let mut problems = vec![];
+        let mut scanners = vec![];
+
+        for (start, lines) in helpers::blocks(input) {
+            let header = lines[0]
+                .strip_prefix("--- scanner ")
+                .and_then(|header| header.strip_suffix(" ---"))
+                .filter(|number| number.parse::<usize>().is_ok());
+            if header.is_none() {
+                let message = format!("expected `--- scanner <n> ---`, found {:?}", lines[0]);
+                problems.push(Problem::new(start, message));
             }
 
-            if line.starts_with("--- scanner ") {
-                scanner = Scanner::default();
-                continue;
+            let beacons = solutions::parse_each(
+                (start + 1..).zip(lines[1..].to_vec()),
+                &mut problems,
+                |line| {
+                    line.parse::<Position>()
+                        .map_err(|_| format!("expected a beacon `x,y,z`, found {:?}", line))
+                },
+            );
+
+            if lines.len() - 1 < 12 {
+                let message = match lines.len() - 1 {
+                    1 => "scanner has 1 beacon, at least 12 are needed".to_string(),
+                    count => format!("scanner has {} beacons, at least 12 are needed", count),
+                };
+                problems.push(Problem::new(start, message));
             }
 
-            let position = line.parse::<Position>().unwrap();
+            scanners.push(Scanner { beacons });
+        }
 
-            scanner.beacons.push(position);
+        if scanners.is_empty() {
+            problems.push(Problem::input("no scanners"));
         }
 
-        result
+        Problems::result(problems, scanners)
     }
 
     fn find(&self, scanner: &Scanner, beacons: &HashSet<Position>) -> Option<Position> {
@@ -188,8 +210,8 @@ impl DaySolution {
             })
     }
 
-    fn solve(&self, input: Option<String>) -> (HashSet<Position>, Vec<Position>) {
-        let scanners = self.parse(input.unwrap());
+    fn solve(&self, input: Option<&str>) -> Result<(HashSet<Position>, Vec<Position>)> {
+        let scanners = self.parse(input.unwrap_or_default())?;
         let first_scanner = scanners.first().unwrap();
 
         let mut beacons = HashSet::<Position>::from_iter(first_scanner.beacons.to_vec());
@@ -219,18 +241,18 @@ impl DaySolution {
             }
         }
 
-        (beacons, offsets)
+        Ok((beacons, offsets))
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input).0.len()))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input)?.0.len()))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
         Ok(Box::new(
-            self.solve(input)
+            self.solve(input)?
                 .1
                 .iter()
                 .tuple_combinations()
@@ -239,6 +261,10 @@ impl Solution for DaySolution {
                 .unwrap(),
         ))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(input))
+    }
 }
 
 #[cfg(test)]
@@ -249,9 +275,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day19_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("79", result.to_string())
     }
@@ -259,10 +283,30 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day19_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("3621", result.to_string())
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day19_demo.txt");
+        assert_eq!(DaySolution.lint(input), vec![]);
+
+        let input = "--- scanner 0 ---\n1,2,3\n4,5\n\n--- scanner one ---\n1,1,1";
+        let problems = DaySolution
+            .lint(input)
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            vec![
+                "line 1: scanner has 2 beacons, at least 12 are needed",
+                "line 3: expected a beacon `x,y,z`, found \"4,5\"",
+                "line 5: expected `--- scanner <n> ---`, found \"--- scanner one ---\"",
+                "line 5: scanner has 1 beacon, at least 12 are needed",
+            ]
+        );
+    }
 }
@@ -1,69 +1,614 @@
-use std::fmt::Display;
-use std::str::FromStr;
+use std::{collections::HashMap, fmt::Display, iter};
 
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use json::object;
 
+use crate::solutions::{self, Options, Problem, Result, Solution, TraceFormat};
+
+#[derive(Default)]
+pub struct DaySolution {
+    /// Target of the planned course, horizontal then depth
+    target: Option<(i64, i64)>,
+    /// Largest value of a planned command, 9 when not set
+    max_value: Option<i64>,
+    model: Model,
+}
+
+/// Position of the submarine, `aim` is also the depth of the part 1 movement model
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Submarine {
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+/// Changes the submarine by the command value, `None` on overflow
+type Apply = fn(&mut Submarine, i64) -> Option<()>;
+
+struct Command {
+    verb: &'static str,
+    takes_value: bool,
+    apply: Apply,
+}
+
+impl Command {
+    /// Command of a course line and its value, 0 for the commands without one
+    fn parse(text: &str) -> core::result::Result<(&'static Command, i64), String> {
+        let mut parts = text.split_whitespace();
+        let verb = parts.next().unwrap_or_default();
+        let command = COMMANDS
+            .iter()
+            .find(|command| command.verb == verb)
+            .ok_or_else(|| format!("unknown command {:?}", verb))?;
+
+        let value = match (parts.next(), command.takes_value) {
+            (Some(value), true) => value
+                .parse::<i64>()
+                .map_err(|err| format!("invalid value {:?}: {}", value, err))?,
+            (None, true) => return Err(format!("{} needs a value", verb)),
+            (Some(_), false) => return Err(format!("{} takes no value", verb)),
+            (None, false) => 0,
+        };
+        if parts.next().is_some() {
+            return Err(format!("unexpected text after {:?}", verb));
+        }
+
+        Ok((command, value))
+    }
+}
+
+const COMMANDS: &[Command] = &[
+    Command {
+        verb: "forward",
+        takes_value: true,
+        apply: |submarine, value| {
+            submarine.horizontal = submarine.horizontal.checked_add(value)?;
+            submarine.depth = submarine
+                .depth
+                .checked_add(submarine.aim.checked_mul(value)?)?;
+            Some(())
+        },
+    },
+    Command {
+        verb: "back",
+        takes_value: true,
+        apply: |submarine, value| {
+            submarine.horizontal = submarine.horizontal.checked_sub(value)?;
+            submarine.depth = submarine
+                .depth
+                .checked_sub(submarine.aim.checked_mul(value)?)?;
+            Some(())
+        },
+    },
+    Command {
+        verb: "down",
+        takes_value: true,
+        apply: |submarine, value| {
+            submarine.aim = submarine.aim.checked_add(value)?;
+            Some(())
+        },
+    },
+    Command {
+        verb: "up",
+        takes_value: true,
+        apply: |submarine, value| {
+            submarine.aim = submarine.aim.checked_sub(value)?;
+            Some(())
+        },
+    },
+    Command {
+        verb: "set-aim",
+        takes_value: true,
+        apply: |submarine, value| {
+            submarine.aim = value;
+            Some(())
+        },
+    },
+    Command {
+        verb: "surface",
+        takes_value: false,
+        apply: |submarine, _| {
+            submarine.depth = 0;
+            submarine.aim = 0;
+            Some(())
+        },
+    },
+];
+
+/// Submarine after the command on `line` of the course
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    pub line: usize,
+    pub command: String,
+    pub submarine: Submarine,
+}
+
+/// Runs course commands one by one, optionally recording every step
 #[derive(Default)]
-pub struct DaySolution;
+pub struct Interpreter {
+    pub submarine: Submarine,
+    trace: Option<Vec<TraceStep>>,
+}
+
+impl Interpreter {
+    pub fn with_trace() -> Interpreter {
+        Self {
+            trace: Some(vec![]),
+            ..Default::default()
+        }
+    }
+
+    pub fn execute(&mut self, line: usize, text: &str) -> Result<()> {
+        let (command, value) = Command::parse(text).map_err(|e| Problem::new(line, e))?;
+
+        let mut submarine = self.submarine;
+        (command.apply)(&mut submarine, value)
+            .ok_or_else(|| Problem::new(line, format!("{:?} overflows", text)))?;
+        self.submarine = submarine;
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceStep {
+                line,
+                command: text.to_string(),
+                submarine,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Executes every non-empty line of `input`
+    pub fn run(&mut self, input: &str) -> Result<Submarine> {
+        for (i, text) in input.lines().enumerate() {
+            if !text.trim().is_empty() {
+                self.execute(i + 1, text.trim())?;
+            }
+        }
+
+        Ok(self.submarine)
+    }
+
+    pub fn trace(&self) -> &[TraceStep] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    pub fn write_trace(&self, format: TraceFormat) -> String {
+        match format {
+            TraceFormat::Csv => {
+                let mut csv = String::from("line,command,horizontal,depth,aim\n");
+                for step in self.trace() {
+                    let Submarine {
+                        horizontal,
+                        depth,
+                        aim,
+                    } = step.submarine;
+                    csv += &format!(
+                        "{},{},{},{},{}\n",
+                        step.line, step.command, horizontal, depth, aim
+                    );
+                }
+                csv
+            }
+            TraceFormat::Json => {
+                let steps = self
+                    .trace()
+                    .iter()
+                    .map(|step| {
+                        object! {
+                            line: step.line,
+                            command: step.command.as_str(),
+                            horizontal: step.submarine.horizontal,
+                            depth: step.submarine.depth,
+                            aim: step.submarine.aim,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                json::JsonValue::Array(steps).pretty(2)
+            }
+        }
+    }
+}
+
+/// Movement model of the course planner, the part 1 or part 2 rules of the day
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Model {
+    Simple,
+    #[default]
+    Aim,
+}
+
+/// Number of commands of at most `max` needed to cover `value`
+fn commands(value: i128, max: i128) -> i128 {
+    (value.abs() + max - 1) / max
+}
+
+/// Aim model plan: the aim before the first forward and the later aim changes along with
+/// the horizontal position they happen at
+type AimPlan = (i128, Vec<(i128, i128)>);
+
+/// Most courses `AimSearch` looks into before giving up
+const SEARCH_LIMIT: usize = 100_000_000;
+
+/// Most commands a planned course is written with
+const COMMAND_LIMIT: i128 = 1_000_000;
+
+/// Steps left, depth still missing when keeping the aim and whether the aim may stay as is
+type Course = (i128, i128, bool);
+
+/// What the search found out about the shortest course of a `Course`
+enum Known {
+    /// No course is shorter
+    AtLeast(i128),
+    /// Its length and changes
+    Shortest(i128, Vec<(i128, i128)>),
+}
+
+/// Exhaustive search of the shortest aim model course.
+///
+/// Any course can be normalized without getting longer: consecutive aim commands merge into
+/// one change of `commands(change)` commands, consecutive forwards into `commands(distance)`
+/// commands, and changes of zero are dropped. A normalized course is a change of the aim
+/// followed either by the forwards to the end or by the forwards to the next change and a
+/// shorter normalized course, which only depends on the steps left and the depth it misses
+/// when keeping the aim. The search tries all of them, skipping the ones that can't beat the
+/// best course found so far, and remembers each result. It is still exponential, so it gives
+/// up after `SEARCH_LIMIT` courses rather than answer with one that may not be the shortest.
+struct AimSearch {
+    max: i128,
+    known: HashMap<Course, Known>,
+    searched: usize,
+}
+
+impl AimSearch {
+    /// Fewest commands of a course: every forward moves at most `max` and every aim command
+    /// adds at most `max` to each of the remaining steps. Unless the first change reaches the
+    /// depth on its own, another change follows and takes at least one more command.
+    fn lower_bound(&self, course: Course) -> i128 {
+        let (left, rest, keep) = course;
+        let last = match rest % left == 0 && (keep || rest != 0) {
+            true => commands(left, self.max) + commands(rest / left, self.max),
+            false => i128::MAX,
+        };
+
+        // Each aim command adds at most `max` less to the steps before a later change
+        let first = !keep as i128;
+        last.min(self.then_change(course, |next, unit| {
+            self.aims(rest, left, self.max * next, unit).max(first + 1)
+        }))
+    }
+
+    /// Fewest aim commands adding `rest` to at most `steps` steps each when they add at least
+    /// `short` less than that in all. A command that isn't a full `max` adds at least `steps`
+    /// less, the others a multiple of `unit` less, so when there is no room for the first kind
+    /// the shortfall has to be a multiple of `unit` or takes one more command.
+    fn aims(&self, rest: i128, steps: i128, short: i128, unit: i128) -> i128 {
+        let count = commands(rest.abs() + short, self.max * steps);
+        let room = count * self.max * steps - rest.abs();
+        count + (room < steps && room % unit != 0) as i128
+    }
+
+    /// Fewest commands of a course that changes the aim again at some `next`, which takes at
+    /// least `aims(next, unit)` aim commands and one more forward unless `next` lines the
+    /// forwards up with the end. `aims` never decreases, so the first `next` of both kinds is
+    /// enough.
+    ///
+    /// When `left` is a multiple of `max`, the forwards only line up when every change is at a
+    /// multiple of `max`, so full aim commands add a multiple of `max * max` less.
+    fn then_change(&self, course: Course, aims: impl Fn(i128, i128) -> i128) -> i128 {
+        let (left, rest, _) = course;
+        let forwards = commands(left, self.max);
+        let (aligned, unit) = match left % self.max {
+            0 if rest % self.max != 0 => (left, self.max),
+            0 => (self.max, self.max * self.max),
+            rest => (rest, self.max),
+        };
+        match left {
+            1 => i128::MAX,
+            _ if aligned < left => {
+                (forwards + 1 + aims(1, self.max)).min(forwards + aims(aligned, unit))
+            }
+            _ => forwards + 1 + aims(1, self.max),
+        }
+    }
+
+    /// Shortest course with fewer than `limit` commands, the changes are relative to its start
+    fn shortest(&mut self, course: Course, mut limit: i128) -> Option<(i128, Vec<(i128, i128)>)> {
+        if self.searched == SEARCH_LIMIT {
+            return None;
+        }
+        self.searched += 1;
+
+        if self.lower_bound(course) >= limit {
+            return None;
+        }
+        match self.known.get(&course) {
+            Some(Known::AtLeast(least)) if *least >= limit => return None,
+            Some(Known::Shortest(length, changes)) => {
+                return (*length < limit).then(|| (*length, changes.clone()));
+            }
+            _ => {}
+        }
+
+        let (left, rest, keep) = course;
+        let forwards = commands(left, self.max);
+        let mut best = None;
 
-enum Step {
-    Forward(i32),
-    Down(i32),
-    Up(i32),
+        // The last change, the remaining forwards reach the depth without stopping
+        if rest % left == 0 && (keep || rest != 0) {
+            let length = commands(rest / left, self.max) + forwards;
+            if length < limit {
+                limit = length;
+                best = Some((length, vec![(0, rest / left)]));
+            }
+        }
+
+        // Otherwise the aim changes again at some `next`, which leaves the same missing depth
+        // to a shorter course
+        let budget = (limit - forwards - 1) * self.max;
+        let center = (rest / left).clamp(-budget.max(0), budget.max(0));
+        let around = (1..=2 * budget).flat_map(|offset| [center - offset, center + offset]);
+        for change in iter::once(center).chain(around) {
+            if self.searched == SEARCH_LIMIT {
+                return None;
+            }
+            self.searched += 1;
+
+            if change.abs() > budget || (change == 0 && !keep) {
+                continue;
+            }
+            let rest = rest - change * left;
+            let length = commands(change, self.max);
+            let tail = self.then_change(course, |next, unit| {
+                self.aims(rest, left - next, 0, unit).max(1)
+            });
+            if rest == 0 || tail == i128::MAX || length + tail >= limit {
+                continue;
+            }
+
+            for next in 1..left {
+                // Later changes only have fewer steps left to reach the depth
+                if length + forwards + commands(rest, self.max * (left - next)) >= limit {
+                    break;
+                }
+                let length = length + commands(next, self.max);
+                if let Some((tail, changes)) =
+                    self.shortest((left - next, rest, false), limit - length)
+                {
+                    limit = length + tail;
+                    best = Some((
+                        limit,
+                        iter::once((0, change))
+                            .chain(changes.into_iter().map(|(at, change)| (next + at, change)))
+                            .collect(),
+                    ));
+                }
+            }
+        }
+
+        if self.searched == SEARCH_LIMIT {
+            return None;
+        }
+        let known = match &best {
+            Some((length, changes)) => Known::Shortest(*length, changes.clone()),
+            None => Known::AtLeast(limit),
+        };
+        self.known.insert(course, known);
+        best
+    }
 }
 
-impl FromStr for Step {
-    type Err = String;
+/// Shortest aim model plan, the search starts from a course with a single change of 1 which
+/// always exists: at `horizontal - depth % horizontal` it leaves a multiple of `horizontal`
+fn plan_aim(horizontal: i128, depth: i128, max: i128) -> Result<AimPlan> {
+    let (initial, rest) = (depth.div_euclid(horizontal), depth.rem_euclid(horizontal));
+    let (plan, length) = match rest {
+        0 => (
+            (initial, vec![]),
+            commands(initial, max) + commands(horizontal, max),
+        ),
+        _ => {
+            let at = horizontal - rest;
+            let length =
+                commands(initial, max) + 1 + commands(at, max) + commands(horizontal - at, max);
+            ((initial, vec![(at, 1)]), length)
+        }
+    };
+
+    let mut search = AimSearch {
+        max,
+        known: HashMap::new(),
+        searched: 0,
+    };
+    let shortest = search.shortest((horizontal, depth, true), length);
+    if search.searched == SEARCH_LIMIT {
+        return Err(format!(
+            "no shortest course found within {} searched courses",
+            SEARCH_LIMIT
+        )
+        .into());
+    }
+
+    Ok(match shortest {
+        Some((_, changes)) => (changes[0].1, changes[1..].to_vec()),
+        None => plan,
+    })
+}
 
-    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
-        let (dir, value) = s.split_once(' ').ok_or("wrong step")?;
-        let value = value.parse::<i32>().map_err(|err| err.to_string())?;
+/// Commands of at most `max` covering every leg, a leg is a value along with the verbs used
+/// at or above zero and below zero. Fails rather than writing more than `COMMAND_LIMIT` lines.
+fn write_commands(legs: &[(i128, &str, &str)], max: i128) -> Result<String> {
+    let count = legs
+        .iter()
+        .map(|&(value, _, _)| commands(value, max))
+        .sum::<i128>();
+    if count > COMMAND_LIMIT {
+        let message = format!(
+            "the course needs {} commands, more than {}",
+            count, COMMAND_LIMIT
+        );
+        return Err(message.into());
+    }
 
-        Ok(match dir {
-            "forward" => Self::Forward(value),
-            "down" => Self::Down(value),
-            "up" => Self::Up(value),
-            _ => unreachable!(),
-        })
+    let mut course = String::new();
+    for &(value, positive, negative) in legs {
+        let verb = if value < 0 { negative } else { positive };
+        let mut value = value.abs();
+        while value > 0 {
+            course += &format!("{} {}\n", verb, value.min(max));
+            value -= value.min(max);
+        }
     }
+
+    Ok(course)
 }
 
 impl DaySolution {
-    fn solve(&self, input: Option<String>) -> (i32, i32, i32) {
-        parse_lines::<Step>(input)
-            .iter()
-            .fold((0, 0, 0), |(horizontal, depth, aim), step| match step {
-                Step::Forward(value) => (horizontal + value, depth + aim * value, aim),
-                Step::Down(value) => (horizontal, depth, aim + value),
-                Step::Up(value) => (horizontal, depth, aim - value),
-            })
+    fn solve(&self, input: Option<&str>) -> Result<Submarine> {
+        Interpreter::default().run(input.unwrap_or_default())
+    }
+
+    /// Shortest course of `forward`, `down` and `up` commands with values up to `max_value`
+    /// reaching the target under `model`, written as the day input
+    pub fn plan(
+        &self,
+        model: Model,
+        horizontal: i64,
+        depth: i64,
+        max_value: i64,
+    ) -> Result<String> {
+        if horizontal < 0 {
+            return Err("the submarine can't move backwards".into());
+        }
+        if max_value < 1 {
+            return Err("commands need a positive maximum value".into());
+        }
+
+        let (horizontal, depth, max) = (horizontal as i128, depth as i128, max_value as i128);
+        // Every course has at least the forward commands, no need to search a course that long
+        write_commands(&[(horizontal, "forward", "forward")], max)?;
+
+        match model {
+            Model::Simple => write_commands(
+                &[(horizontal, "forward", "forward"), (depth, "down", "up")],
+                max,
+            ),
+            Model::Aim if horizontal == 0 => match depth {
+                0 => Ok(String::new()),
+                _ => Err("the depth can't change without moving forward".into()),
+            },
+            Model::Aim => {
+                let (initial, changes) = plan_aim(horizontal, depth, max)?;
+                let mut legs = vec![(initial, "down", "up")];
+
+                let mut position = 0;
+                for (at, change) in changes {
+                    legs.push((at - position, "forward", "forward"));
+                    legs.push((change, "down", "up"));
+                    position = at;
+                }
+                legs.push((horizontal - position, "forward", "forward"));
+
+                write_commands(&legs, max)
+            }
+        }
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (horizontal, _, depth) = self.solve(input);
-        Ok(Box::new(horizontal * depth))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let submarine = self.solve(input)?;
+        let result = submarine
+            .horizontal
+            .checked_mul(submarine.aim)
+            .ok_or("result overflows")?;
+
+        Ok(Box::new(result))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let submarine = self.solve(input)?;
+        let result = submarine
+            .horizontal
+            .checked_mul(submarine.depth)
+            .ok_or("result overflows")?;
+
+        Ok(Box::new(result))
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.target = options.target;
+        self.max_value = options.max_value;
+        self.model = match options.simple {
+            true => Model::Simple,
+            false => Model::Aim,
+        };
+        Ok(())
+    }
+
+    fn plan(&mut self) -> Result<Option<String>> {
+        let (horizontal, depth) = self.target.ok_or("the course needs a target")?;
+        let course = DaySolution::plan(
+            self,
+            self.model,
+            horizontal,
+            depth,
+            self.max_value.unwrap_or(9),
+        )?;
+
+        Ok(Some(course))
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(solutions::parse_lines(input, |line| {
+            Command::parse(line.trim()).map(|_| ())
+        }))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (horizontal, depth, _) = self.solve(input);
-        Ok(Box::new(horizontal * depth))
+    fn trace(&mut self, input: Option<&str>, format: TraceFormat) -> Result<Option<String>> {
+        let mut interpreter = Interpreter::with_trace();
+        interpreter.run(input.unwrap_or_default())?;
+
+        Ok(Some(interpreter.write_trace(format)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day2::DaySolution;
+    use std::collections::{HashSet, VecDeque};
+
+    use crate::day2::{DaySolution, Interpreter, Model, Submarine};
+    use crate::solutions::{Options, TraceFormat};
     use crate::Solution;
 
+    /// Length of the shortest aim model course found by a breadth-first search
+    fn shortest_course(horizontal: i64, depth: i64, max: i64) -> usize {
+        let mut seen = HashSet::from([(0, 0, 0)]);
+        let mut queue = VecDeque::from([((0, 0, 0), 0)]);
+
+        while let Some(((h, aim, d), length)) = queue.pop_front() {
+            if (h, d) == (horizontal, depth) {
+                return length;
+            }
+
+            for value in 1..=max {
+                for next in [
+                    (h + value, aim, d + aim * value),
+                    (h, aim + value, d),
+                    (h, aim - value, d),
+                ] {
+                    if next.0 <= horizontal && next.1.abs() <= 30 && seen.insert(next) {
+                        queue.push_back((next, length + 1));
+                    }
+                }
+            }
+        }
+
+        unreachable!()
+    }
+
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day2_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("150", result.to_string())
     }
@@ -71,12 +616,129 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day2_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("900", result.to_string())
     }
+
+    #[test]
+    fn interpreter() {
+        let mut interpreter = Interpreter::with_trace();
+        let submarine = interpreter
+            .run("down 2\nforward 5\nback 1\nsurface\nset-aim 3\nforward 2\n")
+            .unwrap();
+        assert_eq!(
+            submarine,
+            Submarine {
+                horizontal: 6,
+                depth: 6,
+                aim: 3
+            }
+        );
+        assert_eq!(interpreter.trace().len(), 6);
+        assert_eq!(
+            interpreter.write_trace(TraceFormat::Csv).lines().nth(3),
+            Some("3,back 1,4,8,2")
+        );
+
+        let error = |input| Interpreter::default().run(input).unwrap_err().to_string();
+        assert_eq!(
+            error("forward 1\nsideways 2"),
+            "line 2: unknown command \"sideways\""
+        );
+        assert_eq!(error("surface 2"), "line 1: surface takes no value");
+        assert_eq!(error("down"), "line 1: down needs a value");
+
+        let lines = DaySolution::default()
+            .lint("up x\nforward 1\n\nsideways 2\n")
+            .iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1, 4]);
+        assert_eq!(
+            error("set-aim 9223372036854775807\nforward 2"),
+            "line 2: \"forward 2\" overflows"
+        );
+    }
+
+    #[test]
+    fn plan() {
+        let course = DaySolution::default()
+            .plan(Model::Simple, 15, -10, 4)
+            .unwrap();
+        assert_eq!(course.lines().count(), 7);
+        assert_eq!(
+            "-150",
+            DaySolution::default()
+                .part_1(Some(&course))
+                .unwrap()
+                .to_string()
+        );
+
+        let course = DaySolution::default().plan(Model::Aim, 15, 60, 9).unwrap();
+        assert_eq!(course, "down 4\nforward 9\nforward 6\n");
+        assert_eq!(
+            "900",
+            DaySolution::default()
+                .part_2(Some(&course))
+                .unwrap()
+                .to_string()
+        );
+
+        for horizontal in 1..=6 {
+            for depth in -12..=24 {
+                let course = DaySolution::default()
+                    .plan(Model::Aim, horizontal, depth, 3)
+                    .unwrap();
+                let submarine = Interpreter::default().run(&course).unwrap();
+
+                assert_eq!((submarine.horizontal, submarine.depth), (horizontal, depth));
+                assert_eq!(
+                    course.lines().count(),
+                    shortest_course(horizontal, depth, 3)
+                );
+            }
+        }
+
+        // The shortest course changes the aim by 26 at once, three commands at one stop
+        let course = DaySolution::default()
+            .plan(Model::Aim, 60, 8938, 9)
+            .unwrap();
+        assert_eq!(course.lines().count(), 24);
+        assert_eq!(
+            "536280",
+            DaySolution::default()
+                .part_2(Some(&course))
+                .unwrap()
+                .to_string()
+        );
+
+        assert!(DaySolution::default().plan(Model::Aim, 0, 5, 9).is_err());
+        for model in [Model::Simple, Model::Aim] {
+            let error = DaySolution::default()
+                .plan(model, 1_000_000_000_000, 5, 1)
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "the course needs 1000000000000 commands, more than 1000000"
+            );
+        }
+        assert!(DaySolution::default()
+            .plan(Model::Simple, 600_000, 600_000, 1)
+            .is_err());
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            target: Some((15, 60)),
+            max_value: Some(9),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert_eq!(
+            Solution::plan(&mut solution).unwrap(),
+            Some("down 4\nforward 9\nforward 6\n".to_string())
+        );
+    }
 }
 
 // Bash solution
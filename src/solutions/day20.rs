@@ -1,69 +1,153 @@
-use itertools::iproduct;
 use std::fmt::Display;
 
+use crate::helpers;
+use crate::matrix::bits::BitMatrix;
+use crate::matrix::image::{Rgb, BLACK, WHITE};
 use crate::matrix::Matrix;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
-type Image = Matrix<u8>;
+/// Lit pixels of the image, the infinite pixels around it are all `background`
+struct Image {
+    pixels: BitMatrix,
+    background: bool,
+}
+
+impl Image {
+    /// The image grows by one pixel on every side, as those pixels see the image in their window
+    fn enhance(&self, enhancement: &[bool]) -> Image {
+        let mut pixels = BitMatrix::new(self.pixels.width + 2, self.pixels.height + 2);
+        for y in 0..pixels.height {
+            for x in 0..pixels.width {
+                let index = self
+                    .pixels
+                    .window3(x as i64 - 1, y as i64 - 1, self.background);
+                pixels.set(x, y, enhancement[index]);
+            }
+        }
+
+        Image {
+            pixels,
+            background: enhancement[if self.background { 511 } else { 0 }],
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct DaySolution {}
 
 impl DaySolution {
-    fn solve(&self, input: Option<String>, steps: usize) -> usize {
-        let input = input.unwrap();
-        let (enhancement, image_data) = input.split_once("\n\n").unwrap();
-        let enhancement = enhancement.chars().collect::<Vec<_>>();
-        let image = Image::from(&image_data.replace('#', "1").replace('.', "0")).unwrap();
-
-        let mut new_image = Image::new(image.width + steps * 2, image.height + steps * 2);
-        image.iter().for_each(|(value, (x, y))| {
-            new_image.set(x + steps, y + steps, *value);
-        });
-
-        for step in 0..steps {
-            new_image = Image::from_iter(
-                new_image.iter().map(|(_, (x, y))| {
-                    let index = iproduct!(-1..=1, -1..=1).fold(0usize, |result, (dy, dx)| {
-                        (result << 1)
-                            + match new_image.get(x as i32 + dx, y as i32 + dy) {
-                                Some(x) => *x as usize,
-                                None if enhancement[0] == '.' => 0,
-                                None => step % 2,
-                            }
-                    });
-
-                    (enhancement[index] == '#').then(|| &1).unwrap_or(&0)
-                }),
-                new_image.width,
-            );
+    /// The 512 pixels of the enhancement algorithm, then the image after an empty line
+    fn parse(&self, input: &str) -> Result<(Vec<bool>, Image)> {
+        let blocks = helpers::blocks(input);
+        let mut problems = vec![];
+
+        let enhancement = match blocks.first() {
+            Some((start, lines)) => {
+                let line = lines[0];
+                if lines.len() > 1 {
+                    problems.push(Problem::new(
+                        start + 1,
+                        "the enhancement algorithm must be on one line",
+                    ));
+                }
+                if let Some(ch) = line.chars().find(|&ch| ch != '#' && ch != '.') {
+                    problems.push(Problem::new(*start, format!("invalid pixel {:?}", ch)));
+                } else if line.len() != 512 {
+                    let message = format!(
+                        "the enhancement algorithm must have 512 pixels, found {}",
+                        line.len()
+                    );
+                    problems.push(Problem::new(*start, message));
+                }
+                line.chars().map(|ch| ch == '#').collect::<Vec<_>>()
+            }
+            None => {
+                problems.push(Problem::input("missing the enhancement algorithm"));
+                vec![]
+            }
+        };
+
+        let pixels = match blocks.get(1) {
+            Some((start, lines)) => Matrix::parse_with(&lines.join("\n"), |ch| match ch {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            })
+            .map_err(|error| {
+                let found = solutions::problems(error.into());
+                problems.extend(found.into_iter().map(|problem| problem.within(*start)));
+            })
+            .ok(),
+            None => {
+                let line = input.lines().count() + 1;
+                problems.push(Problem::new(line, "missing the image"));
+                None
+            }
+        };
+
+        if let Some((start, _)) = blocks.get(2) {
+            problems.push(Problem::new(*start, "unexpected lines after the image"));
         }
 
-        new_image.iter().filter(|(&v, _)| v == 1).count()
+        let image = Image {
+            pixels: pixels.map_or_else(
+                || BitMatrix::new(0, 0),
+                |image| BitMatrix::from_matrix(&image, |&lit| lit),
+            ),
+            background: false,
+        };
+
+        Problems::result(problems, (enhancement, image))
+    }
+
+    fn solve(&self, input: Option<&str>, steps: usize) -> Result<Image> {
+        let (enhancement, mut image) = self.parse(input.unwrap_or_default())?;
+        for _ in 0..steps {
+            image = image.enhance(&enhancement);
+        }
+
+        Ok(image)
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 2)))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let image = self.solve(input, 2)?;
+        Ok(Box::new(image.pixels.count_ones()))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let image = self.solve(input, 50)?;
+        Ok(Box::new(image.pixels.count_ones()))
+    }
+
+    fn render(&mut self, input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        let image = self.solve(input, 50)?;
+
+        Ok(Some(image.pixels.to_matrix().map(|&lit| {
+            if lit {
+                WHITE
+            } else {
+                BLACK
+            }
+        })))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 50)))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(input))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day20::DaySolution;
+    use crate::solutions::Problem;
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day20_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("35", result.to_string());
     }
@@ -71,10 +155,24 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day20_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("3351", result.to_string());
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day20_demo.txt");
+        assert_eq!(vec![] as Vec<Problem>, DaySolution::default().lint(input));
+
+        let problems = DaySolution::default().lint("#..#\n\n#.\n.x\n");
+        let problems = problems.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "line 1: the enhancement algorithm must have 512 pixels, found 4",
+                "line 4: column 2: invalid cell \"x\"",
+            ],
+            problems
+        );
+    }
 }
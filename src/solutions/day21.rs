@@ -3,7 +3,7 @@ use memoize::memoize;
 use std::cmp::max;
 use std::fmt::Display;
 
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -23,17 +23,27 @@ impl Dice {
 }
 
 impl DaySolution {
-    fn parse(&self, input: Option<String>) -> (usize, usize) {
-        let input = input
-            .unwrap()
-            .replace("Player 1 starting position: ", "")
-            .replace("Player 2 starting position: ", "");
-
-        let (player1, player2) = input.split_once('\n').unwrap();
-        (
-            player1.parse().unwrap_or_default(),
-            player2.parse().unwrap_or_default(),
-        )
+    fn parse(&self, input: Option<&str>) -> Result<(usize, usize)> {
+        let positions = solutions::parse_lines(input.unwrap_or_default(), |line| {
+            line.trim()
+                .strip_prefix("Player ")
+                .and_then(|line| line.split_once(" starting position: "))
+                .and_then(|(_, position)| position.parse::<usize>().ok())
+                .filter(|position| (1..=10).contains(position))
+                .ok_or_else(|| {
+                    format!(
+                        "expected `Player <n> starting position: <1-10>`, found {:?}",
+                        line
+                    )
+                })
+        })?;
+
+        match positions[..] {
+            [player1, player2] => Ok((player1, player2)),
+            _ => {
+                Err(Problem::input(format!("expected 2 players, found {}", positions.len())).into())
+            }
+        }
     }
 }
 
@@ -79,8 +89,8 @@ fn play(dice: &mut Dice, playing: Player, waiting: Player) -> usize {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (player1, player2) = self.parse(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (player1, player2) = self.parse(input)?;
 
         Ok(Box::new(play(
             &mut Dice::default(),
@@ -89,12 +99,16 @@ impl Solution for DaySolution {
         )))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let (player1, player2) = self.parse(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let (player1, player2) = self.parse(input)?;
         let result = dirac(0, 0, player1, player2);
 
         Ok(Box::new(max(result.0, result.1)))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
@@ -105,9 +119,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day21_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("739785", result.to_string());
     }
@@ -115,9 +127,7 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day21_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("444356092776315", result.to_string());
     }
@@ -1,7 +1,8 @@
-use crate::solutions::{Result, Solution};
+use crate::matrix::grid::Grid3;
+use crate::solutions::{self, Problem, Result, Solution};
 use euclid::{Box3D, Point3D};
+use itertools::iproduct;
 use std::cmp::{max, min};
-use std::collections::HashSet;
 use std::fmt::Display;
 
 #[derive(Default)]
@@ -58,52 +59,46 @@ struct Rule {
     status: bool,
 }
 
-impl DaySolution {
-    fn parse(&self, input: Option<String>) -> Vec<Rule> {
-        let input = input.unwrap();
-        let mut rules = vec![];
-
-        for line in input.lines() {
-            let (status, rest) = line.split_once(' ').unwrap();
-            let status = status == "on";
-
-            let result = rest
-                .replace("x=", "")
-                .replace("y=", "")
-                .replace("z=", "")
-                .split(',')
-                .map(|str| {
-                    let (mn, mx) = str.split_once("..").unwrap();
-                    let mn = mn.parse::<isize>().unwrap();
-                    let mx = mx.parse::<isize>().unwrap();
-
-                    (mn, mx)
-                })
-                .collect::<Vec<_>>();
-
-            let cuboid = CuboidBox::new(
-                Point3D::new(result[0].0, result[1].0, result[2].0),
-                Point3D::new(result[0].1, result[1].1, result[2].1),
-            );
-
-            rules.push(Rule { cuboid, status })
+impl Rule {
+    /// `None` when the rule is malformed or a range is reversed
+    fn parse(line: &str) -> Option<Rule> {
+        let (status, rest) = line.split_once(' ')?;
+        let status = match status {
+            "on" => true,
+            "off" => false,
+            _ => return None,
+        };
+
+        let mut ranges = ["x=", "y=", "z="]
+            .iter()
+            .zip(rest.split(','))
+            .map(|(axis, range)| {
+                let (mn, mx) = range.strip_prefix(axis)?.split_once("..")?;
+                let (mn, mx) = (mn.parse::<isize>().ok()?, mx.parse::<isize>().ok()?);
+
+                (mn <= mx).then_some((mn, mx))
+            });
+        let (x, y, z) = (ranges.next()??, ranges.next()??, ranges.next()??);
+        if rest.split(',').count() != 3 {
+            return None;
         }
 
-        rules
-    }
-
-    fn generate(&self, cuboid: &CuboidBox) -> Vec<CuboidBox> {
-        let mut result = vec![];
+        let cuboid = CuboidBox::new(Point3D::new(x.0, y.0, z.0), Point3D::new(x.1, y.1, z.1));
 
-        for x in cuboid.min.x..=cuboid.max.x {
-            for y in cuboid.min.y..=cuboid.max.y {
-                for z in cuboid.min.z..=cuboid.max.z {
-                    result.push(CuboidBox::new(Point3D::new(x, y, z), Point3D::new(x, y, z)))
-                }
-            }
-        }
+        Some(Rule { cuboid, status })
+    }
+}
 
-        result
+impl DaySolution {
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Rule>> {
+        solutions::parse_lines(input.unwrap_or_default(), |line| {
+            Rule::parse(line.trim()).ok_or_else(|| {
+                format!(
+                    "expected a rule `on|off x=<a>..<b>,y=<c>..<d>,z=<e>..<f>`, found {:?}",
+                    line
+                )
+            })
+        })
     }
 
     fn resize(&self, cuboid: &CuboidBox) -> CuboidBox {
@@ -122,26 +117,26 @@ impl DaySolution {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let rules = self.parse(input);
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let rules = self.parse(input)?;
 
-        let mut result = HashSet::new();
+        let mut reactor = Grid3::<bool>::new([101, 101, 101]);
         for rule in rules {
             let resized = self.resize(&rule.cuboid);
-            for item in self.generate(&resized) {
-                if rule.status {
-                    result.insert(item);
-                } else {
-                    result.remove(&item);
-                }
+            for (x, y, z) in iproduct!(
+                resized.min.x..=resized.max.x,
+                resized.min.y..=resized.max.y,
+                resized.min.z..=resized.max.z
+            ) {
+                reactor.set([x as i32 + 50, y as i32 + 50, z as i32 + 50], rule.status);
             }
         }
 
-        Ok(Box::new(result.len()))
+        Ok(Box::new(reactor.iter().filter(|(&on, _)| on).count()))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let rules = self.parse(input);
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let rules = self.parse(input)?;
 
         let mut cuboids: Vec<Cuboid> = vec![];
 
@@ -162,6 +157,10 @@ impl Solution for DaySolution {
 
         Ok(Box::new(cuboids.iter().map(Cuboid::count).sum::<isize>()))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 // #[cfg(test)]
@@ -173,7 +172,7 @@ impl Solution for DaySolution {
 //     fn part_1() {
 //         let input = include_str!("../../inputs/day22_demo.txt");
 //         let result = DaySolution::default()
-//             .part_1(Some(input.to_string()))
+//             .part_1(Some(input))
 //             .unwrap();
 //
 //         assert_eq!("", result.to_string())
@@ -183,7 +182,7 @@ impl Solution for DaySolution {
 //     fn part_2() {
 //         let input = include_str!("../../inputs/day22_demo.txt");
 //         let result = DaySolution::default()
-//             .part_2(Some(input.to_string()))
+//             .part_2(Some(input))
 //             .unwrap();
 //
 //         assert_eq!("", result.to_string())
@@ -1,4 +1,4 @@
-use itertools::Itertools;
+use itertools::{izip, Itertools};
 use std::cmp::{max, min};
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -8,7 +8,7 @@ use std::time::Duration;
 use lazy_static::lazy_static;
 use pathfinding::prelude::dijkstra;
 
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -33,8 +33,8 @@ type State = (Rooms, Hallway);
 const AMPHIPOD_HALLWAY_MOVES: [usize; 7] = [0, 1, 3, 5, 7, 9, 10];
 
 impl DaySolution {
-    fn solve(&self, input: Option<String>, animate: bool) -> String {
-        let initial_state = self.parse(input);
+    fn solve(&self, input: Option<&str>, animate: bool) -> Result<String> {
+        let initial_state = self.parse(input)?;
         let room_size = initial_state[0].len();
         let final_state = [
             vec!['A'; room_size],
@@ -154,24 +154,67 @@ impl DaySolution {
                 println!();
             }
 
-            return cost.to_string();
+            return Ok(cost.to_string());
         }
 
-        "No solution".to_string()
+        Ok("No solution".to_string())
     }
 
     fn distance(&self, room_index: usize, position: usize) -> usize {
-        (position as i32 - (room_index as i32 + 1) * 2).abs() as usize
+        (position as i32 - (room_index as i32 + 1) * 2).unsigned_abs() as usize
     }
 
-    fn parse(&self, input: Option<String>) -> Rooms {
-        let input = input.unwrap();
-        let lines = input
-            .lines()
-            .map(|s| s.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+    /// Burrow diagram with rooms of any depth, each type of amphipod filling exactly one room
+    fn parse(&self, input: Option<&str>) -> Result<Rooms> {
+        let lines = input.unwrap_or_default().lines().collect::<Vec<_>>();
+        let mut problems = vec![];
+
+        if lines.len() < 5 {
+            let message = format!("expected at least 5 lines, found {}", lines.len());
+            return Err(Problem::new(lines.len().max(1), message).into());
+        }
+
+        // `X` stands for an amphipod
+        let layout = ["#############", "#...........#", "###X#X#X#X###"]
+            .into_iter()
+            .chain(vec!["  #X#X#X#X#"; lines.len() - 4])
+            .chain(["  #########"]);
+
+        for (i, (line, expected)) in lines.iter().zip(layout).enumerate() {
+            let matching = line.len() == expected.len()
+                && line.chars().zip(expected.chars()).all(|(ch, e)| match e {
+                    'X' => ('A'..='D').contains(&ch),
+                    _ => ch == e,
+                });
+            if !matching {
+                let message = format!("expected {:?}, found {:?}", expected, line);
+                problems.push(Problem::new(i + 1, message));
+            }
+        }
+
+        let amphipods = lines[2..lines.len() - 1]
+            .iter()
+            .flat_map(|line| line.chars())
+            .filter(|ch| ('A'..='D').contains(ch))
+            .counts();
         let size = lines.len() - 3;
+        for amphipod in 'A'..='D' {
+            let count = amphipods.get(&amphipod).copied().unwrap_or(0);
+            if count != size {
+                let message = format!(
+                    "expected {} amphipods of type {}, found {}",
+                    size, amphipod, count
+                );
+                problems.push(Problem::new(3, message));
+            }
+        }
+
+        Problems::result(problems, ())?;
 
+        let lines = lines
+            .iter()
+            .map(|s| s.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
         let mut rooms = [
             vec!['.'; size],
             vec!['.'; size],
@@ -185,7 +228,7 @@ impl DaySolution {
             }
         }
 
-        rooms
+        Ok(rooms)
     }
 
     fn draw(&self, state: &State) -> String {
@@ -194,11 +237,8 @@ impl DaySolution {
         output.push("╭───────────╮".to_string());
         output.push(format!("│{}│", hallway.iter().collect::<String>()));
         output.push("╰─╮ ┬ ┬ ┬ ╭─╯".to_string());
-        for level in 0..rooms[0].len() {
-            output.push(format!(
-                "  │{}│{}│{}│{}│",
-                rooms[0][level], rooms[1][level], rooms[2][level], rooms[3][level]
-            ));
+        for (a, b, c, d) in izip!(&rooms[0], &rooms[1], &rooms[2], &rooms[3]) {
+            output.push(format!("  │{}│{}│{}│{}│", a, b, c, d));
         }
         output.push("  ╰─┴─┴─┴─╯".to_string());
         output.join("\n")
@@ -206,28 +246,33 @@ impl DaySolution {
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, true)))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input, true)?))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input, true)?))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, true)))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day23::DaySolution;
+    use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day23_demo.txt");
-        let result = DaySolution::default().solve(Some(input.to_string()), false);
+        let result = DaySolution::default().solve(Some(input), false).unwrap();
 
         assert_eq!("12521", result);
 
         let input = include_str!("../../inputs/day23.txt");
-        let result = DaySolution::default().solve(Some(input.to_string()), false);
+        let result = DaySolution::default().solve(Some(input), false).unwrap();
 
         assert_eq!("18195", result);
     }
@@ -235,13 +280,34 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day23_demo_2.txt");
-        let result = DaySolution::default().solve(Some(input.to_string()), false);
+        let result = DaySolution::default().solve(Some(input), false).unwrap();
 
         assert_eq!("44169", result);
 
         let input = include_str!("../../inputs/day23_2.txt");
-        let result = DaySolution::default().solve(Some(input.to_string()), false);
+        let result = DaySolution::default().solve(Some(input), false).unwrap();
 
         assert_eq!("50265", result);
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day23_2.txt");
+        assert_eq!(DaySolution.lint(input), vec![]);
+
+        let input = "#############\n#...........#\n###B#C#B#D###\n  #A#D#C#E#\n  ########";
+        let problems = DaySolution
+            .lint(input)
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            vec![
+                "line 3: expected 2 amphipods of type A, found 1",
+                "line 4: expected \"  #X#X#X#X#\", found \"  #A#D#C#E#\"",
+                "line 5: expected \"  #########\", found \"  ########\"",
+            ]
+        );
+    }
 }
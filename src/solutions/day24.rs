@@ -1,72 +1,122 @@
-use std::collections::VecDeque;
 use std::fmt::Display;
 
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Problems, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
 
-impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let blocks = input
-            .unwrap()
-            .split("inp w\n")
-            .skip(1)
-            .map(|s| s.lines().map(String::from).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+/// Instructions run for every digit of the model number, `_` stands for the digit's parameter
+const BLOCK: [&str; 18] = [
+    "inp w", "mul x 0", "add x z", "mod x 26", "div z _", "add x _", "eql x w", "eql x 0",
+    "mul y 0", "add y 25", "mul y x", "add y 1", "mul z y", "mul y 0", "add y w", "add y _",
+    "mul y x", "add z y",
+];
+
+/// Digits `a` and `b` of the model number must satisfy `model[b] == model[a] + difference`
+struct Constraint {
+    a: usize,
+    b: usize,
+    difference: i32,
+}
+
+impl DaySolution {
+    /// The 14 blocks of the MONAD program, each either pushing a digit on `z` or popping one
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Constraint>> {
+        let lines = input.unwrap_or_default().lines().collect::<Vec<_>>();
+        if lines.len() != 14 * BLOCK.len() {
+            let message = format!(
+                "expected 14 blocks of {} instructions, found {} lines",
+                BLOCK.len(),
+                lines.len()
+            );
+            return Err(Problem::input(message).into());
+        }
+
+        let mut problems = vec![];
+        let mut constraints = vec![];
+        let mut stack = vec![];
+        for (index, block) in lines.chunks(BLOCK.len()).enumerate() {
+            let start = index * BLOCK.len() + 1;
+            let mut parameters = vec![];
+            for (i, (line, expected)) in block.iter().zip(BLOCK).enumerate() {
+                let parameter = match expected.strip_suffix('_') {
+                    Some(prefix) => line
+                        .strip_prefix(prefix)
+                        .and_then(|value| value.parse::<i32>().ok()),
+                    None => (line.trim() == expected).then_some(0),
+                };
+                match parameter {
+                    Some(value) => parameters.push(value),
+                    None => {
+                        let message = format!("expected `{}`, found {:?}", expected, line);
+                        problems.push(Problem::new(start + i, message));
+                    }
+                }
+            }
+            if parameters.len() != BLOCK.len() {
+                continue;
+            }
+
+            let (divide, check, offset) = (parameters[4], parameters[5], parameters[15]);
+            match divide {
+                1 => stack.push((index, offset)),
+                26 => match stack.pop() {
+                    Some((a, offset)) => constraints.push(Constraint {
+                        a,
+                        b: index,
+                        difference: offset + check,
+                    }),
+                    None => problems.push(Problem::new(start + 4, "no earlier digit to pair with")),
+                },
+                _ => problems.push(Problem::new(start + 4, "expected `div z 1` or `div z 26`")),
+            }
+        }
 
+        if problems.is_empty() && !stack.is_empty() {
+            problems.push(Problem::input(format!(
+                "{} digits are never paired",
+                stack.len()
+            )));
+        }
+
+        Problems::result(problems, constraints)
+    }
+}
+
+impl Solution for DaySolution {
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
         let mut model = [0; 14];
-        let mut stack = VecDeque::new();
-        for (index, block) in blocks.iter().enumerate() {
-            if block[3] == "div z 1" {
-                let y = block[14].replace("add y ", "").parse::<i32>().unwrap();
-                stack.push_front((index, y));
+        for Constraint { a, b, difference } in self.parse(input)? {
+            if difference > 0 {
+                model[b] = 9;
+                model[a] = 9 - difference;
             } else {
-                let (model_num_position, y) = stack.pop_front().unwrap();
-                let x = block[4].replace("add x ", "").parse::<i32>().unwrap();
-                if x + y > 0 {
-                    model[index] = 9;
-                    model[model_num_position] = 9 - x - y;
-                } else {
-                    model[index] = 9 + x + y;
-                    model[model_num_position] = 9;
-                }
+                model[b] = 9 + difference;
+                model[a] = 9;
             }
         }
 
         Ok(Box::new(model.map(|v| v.to_string()).join("")))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let blocks = input
-            .unwrap()
-            .split("inp w\n")
-            .skip(1)
-            .map(|s| s.lines().map(String::from).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
         let mut model = [0; 14];
-        let mut stack = VecDeque::new();
-        for (index, block) in blocks.iter().enumerate() {
-            if block[3] == "div z 1" {
-                let y = block[14].replace("add y ", "").parse::<i32>().unwrap();
-                stack.push_front((index, y));
+        for Constraint { a, b, difference } in self.parse(input)? {
+            if difference > 0 {
+                model[b] = 1 + difference;
+                model[a] = 1;
             } else {
-                let (model_num_position, y) = stack.pop_front().unwrap();
-                let x = block[4].replace("add x ", "").parse::<i32>().unwrap();
-
-                if x + y > 0 {
-                    model[index] = 1 + x + y;
-                    model[model_num_position] = 1;
-                } else {
-                    model[index] = 1;
-                    model[model_num_position] = 1 - x - y;
-                }
+                model[b] = 1;
+                model[a] = 1 - difference;
             }
         }
 
         Ok(Box::new(model.map(|v| v.to_string()).join("")))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 // #[cfg(test)]
@@ -78,7 +128,7 @@ impl Solution for DaySolution {
 //     fn part_1() {
 //         let input = include_str!("../../inputs/day24_demo.txt");
 //         let result = DaySolution::default()
-//             .part_1(Some(input.to_string()))
+//             .part_1(Some(input))
 //             .unwrap();
 //
 //         assert_eq!("", result.to_string())
@@ -88,7 +138,7 @@ impl Solution for DaySolution {
 //     fn part_2() {
 //         let input = include_str!("../../inputs/day24_demo.txt");
 //         let result = DaySolution::default()
-//             .part_2(Some(input.to_string()))
+//             .part_2(Some(input))
 //             .unwrap();
 //
 //         assert_eq!("", result.to_string())
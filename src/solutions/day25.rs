@@ -1,95 +1,145 @@
+use crate::matrix::automaton::{Automaton, Border, Neighbourhood};
+use crate::matrix::parallel::threads;
 use crate::matrix::Matrix;
-use crate::solutions::{Result, Solution};
-use std::collections::HashSet;
+use crate::solutions::{self, Options, Problem, Result, Solution};
 use std::fmt::Display;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    /// Steps already made by the sea cucumbers of the input
+    start_step: usize,
+}
 
 type Sea = Matrix<char>;
 
 impl DaySolution {
-    fn r#move(&self, sea: &mut Sea, cucumber: char) -> bool {
-        let next = if cucumber == '>' {
-            (1, 0, 0, 1)
-        } else {
-            (0, 1, 1, 0)
-        };
-
-        let mut moved = HashSet::new();
-        sea.iter()
-            .filter(|(&c, _)| c == cucumber)
-            .map(|(_, pos)| pos)
-            .collect::<Vec<_>>()
-            .iter()
-            .fold(false, |result, (x, y)| {
-                let x = *x as i32;
-                let y = *y as i32;
-
-                let new_pos = match (
-                    sea.get(x + next.0, y + next.1),
-                    sea.get(x * next.2, y * next.3),
-                ) {
-                    (Some('.'), _) if !moved.contains(&(x + next.0, y + next.1)) => {
-                        Some((x + next.0, y + next.1))
-                    }
-                    (None, Some('.')) if !moved.contains(&(x * next.2, y * next.3)) => {
-                        Some((x * next.2, y * next.3))
-                    }
-                    _ => None,
-                };
-
-                if let Some((new_x, new_y)) = new_pos {
-                    sea.set(x, y, '.');
-                    sea.set(new_x, new_y, cucumber);
-                    moved.insert((x, y));
-                    return true;
-                }
-
-                result
-            })
+    fn parse(input: Option<&str>) -> Result<Sea> {
+        let sea = Sea::parse_with(input.unwrap_or_default(), |ch| match ch {
+            '>' | 'v' | '.' => Some(ch),
+            _ => None,
+        })?;
+
+        Ok(sea)
+    }
+
+    /// Sea cucumbers of the input, moved row by row on `threads` threads
+    fn herd(input: Option<&str>, threads: usize) -> Result<Automaton<char>> {
+        Ok(Automaton::new(Self::parse(input)?, Border::Wrap)?.parallel(threads))
+    }
+
+    /// Moves every `cucumber` whose next cell at `(dx, dy)` is empty
+    fn r#move(neighbourhood: &Neighbourhood<char>, cucumber: char, (dx, dy): (i32, i32)) -> char {
+        match (
+            neighbourhood.get(-dx, -dy),
+            neighbourhood.cell(),
+            neighbourhood.get(dx, dy),
+        ) {
+            (&c, '.', _) if c == cucumber => cucumber,
+            (_, &c, '.') if c == cucumber => '.',
+            (_, &c, _) => c,
+        }
+    }
+
+    fn east(neighbourhood: &Neighbourhood<char>) -> char {
+        Self::r#move(neighbourhood, '>', (1, 0))
+    }
+
+    fn south(neighbourhood: &Neighbourhood<char>) -> char {
+        Self::r#move(neighbourhood, 'v', (0, 1))
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let mut sea = Sea::from(&input.unwrap()).unwrap();
-
-        Ok(Box::new(
-            std::iter::repeat(1)
-                .take_while(|_| self.r#move(&mut sea, '>') | self.r#move(&mut sea, 'v'))
-                .count()
-                + 1,
-        ))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let mut sea = Self::herd(input, threads())?;
+
+        let steps = sea.run_phases_until_stable(&[&Self::east, &Self::south]);
+        // A herd that no longer moves looks the same at every step since it stopped
+        if steps == 1 && self.start_step > 0 {
+            let message = format!(
+                "the sea cucumbers already stopped moving by step {}",
+                self.start_step
+            );
+            return Err(message.into());
+        }
+
+        Ok(Box::new(self.start_step + steps))
     }
 
-    fn part_2(&mut self, _input: Option<String>) -> Result<Box<dyn Display>> {
+    fn part_2(&mut self, _input: Option<&str>) -> Result<Box<dyn Display>> {
         Ok(Box::new("NO PART 2"))
     }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.start_step = options.start_step;
+        Ok(())
+    }
+
+    fn snapshot(&mut self, input: Option<&str>, steps: usize) -> Result<Option<Matrix<char>>> {
+        let mut sea = Self::herd(input, threads())?;
+        (0..steps).for_each(|_| {
+            sea.step_phases(&[&Self::east, &Self::south]);
+        });
+
+        Ok(Some(sea.grid))
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(Self::parse(Some(input)))
+    }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use crate::day25::DaySolution;
-//     use crate::Solution;
-//
-//     #[test]
-//     fn part_1() {
-//         let input = include_str!("../../inputs/day25_demo.txt");
-//         let result = DaySolution::default()
-//             .part_1(Some(input.to_string()))
-//             .unwrap();
-//
-//         assert_eq!("", result.to_string())
-//     }
-//
-//     #[test]
-//     fn part_2() {
-//         let input = include_str!("../../inputs/day25_demo.txt");
-//         let result = DaySolution::default()
-//             .part_2(Some(input.to_string()))
-//             .unwrap();
-//
-//         assert_eq!("", result.to_string())
-//     }
-// }
+#[cfg(test)]
+mod tests {
+    use crate::day25::DaySolution;
+    use crate::matrix::diff::assert_matrix_eq;
+    use crate::solutions::Options;
+    use crate::Solution;
+
+    #[test]
+    fn part_1() {
+        let input = include_str!("../../inputs/day25_demo.txt");
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
+
+        assert_eq!("58", result.to_string())
+    }
+
+    #[test]
+    fn resume() {
+        let input = include_str!("../../inputs/day25_demo.txt");
+
+        for (start_step, result) in [(30, Some(58)), (57, None), (80, None)] {
+            let grid = DaySolution::default()
+                .snapshot(Some(input), start_step)
+                .unwrap()
+                .unwrap()
+                .to_string();
+
+            let mut solution = DaySolution::default();
+            let options = Options {
+                start_step,
+                ..Options::default()
+            };
+            solution.configure(&options).unwrap();
+            let resumed = solution.part_1(Some(&grid)).ok();
+            assert_eq!(
+                resumed.map(|r| r.to_string()),
+                result.map(|r| r.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn parallel() {
+        let input = Some(include_str!("../../inputs/day25_demo.txt"));
+        let mut serial = DaySolution::herd(input, 1).unwrap();
+        let mut parallel = DaySolution::herd(input, 4).unwrap();
+
+        let phases = [&DaySolution::east as _, &DaySolution::south as _];
+        assert_eq!(
+            serial.run_phases_until_stable(&phases),
+            parallel.run_phases_until_stable(&phases)
+        );
+        assert_matrix_eq!(serial.grid, parallel.grid);
+    }
+}
@@ -1,106 +1,284 @@
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
-use std::fmt::Display;
+use crate::solutions::{self, Options, Problem, Result, Solution};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    ties: TiePolicy,
+}
 
-trait BitString {
-    fn bit_is_set(&self, at: usize) -> bool;
+/// Bit kept when both values are equally common
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    Zero,
+    One,
 }
 
-impl BitString for String {
-    fn bit_is_set(&self, at: usize) -> bool {
-        self.as_bytes()[at] == b'1'
+impl TieBreak {
+    fn bit(self) -> bool {
+        self == TieBreak::One
     }
 }
 
-impl DaySolution {
-    fn count_bits(&self, input: &[String], at: usize) -> (i32, i32) {
-        input.iter().fold((0, 0), |bits, line| {
-            let bit = line.bit_is_set(at) as i32;
-            (bits.0 + 1 - bit, bits.1 + bit)
-        })
-    }
-
-    fn retain_at_least_one<T, F>(&self, arr: &mut Vec<T>, mut func: F)
-    where
-        F: FnMut(&T) -> bool,
-    {
-        let mut count = arr.len();
-        if count == 1 {
-            return;
+/// Tie-breaking of every most/least common bit decision, the gamma one also decides epsilon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiePolicy {
+    pub gamma: TieBreak,
+    pub oxygen: TieBreak,
+    pub co2: TieBreak,
+}
+
+impl Default for TiePolicy {
+    fn default() -> Self {
+        Self {
+            gamma: TieBreak::Zero,
+            oxygen: TieBreak::One,
+            co2: TieBreak::Zero,
         }
+    }
+}
+
+/// Bits kept by gamma, oxygen and CO2 in that order, `010` being the default policy
+impl FromStr for TiePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let ties = s
+            .chars()
+            .map(|bit| match bit {
+                '0' => Ok(TieBreak::Zero),
+                '1' => Ok(TieBreak::One),
+                _ => Err(format!("invalid tie bit {:?}", bit)),
+            })
+            .collect::<core::result::Result<Vec<_>, _>>()?;
 
-        arr.retain(|elem| {
-            let is_deleted = func(elem);
-            count -= !is_deleted as usize;
-            count == 0 || is_deleted
-        })
+        match ties[..] {
+            [gamma, oxygen, co2] => Ok(Self { gamma, oxygen, co2 }),
+            _ => Err(format!("expected 3 tie bits, found {}", ties.len())),
+        }
     }
 }
 
-impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines(input);
-        let len = lines.first().map(String::len).unwrap_or(0);
-        let mut gamma = 0;
-        let mut epsilon = 0;
+/// One bit criteria filter step, `bit` counts from the most significant bit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FilterRound {
+    pub bit: usize,
+    pub zeros: usize,
+    pub ones: usize,
+    pub kept: bool,
+    pub remaining: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticReport {
+    pub width: usize,
+    pub gamma: u128,
+    pub epsilon: u128,
+    pub oxygen: u128,
+    pub co2: u128,
+    pub oxygen_rounds: Vec<FilterRound>,
+    pub co2_rounds: Vec<FilterRound>,
+}
+
+impl DiagnosticReport {
+    pub fn power_consumption(&self) -> Result<u128> {
+        Ok(self
+            .gamma
+            .checked_mul(self.epsilon)
+            .ok_or("power consumption overflows")?)
+    }
 
-        for i in 0..len {
-            let bits = self.count_bits(&lines, i);
-            let bit = (bits.1 > bits.0) as i32;
+    pub fn life_support(&self) -> Result<u128> {
+        Ok(self
+            .oxygen
+            .checked_mul(self.co2)
+            .ok_or("life support rating overflows")?)
+    }
+}
 
-            gamma |= bit << (len - i - 1);
-            epsilon |= (1 - bit) << (len - i - 1);
+impl Display for DiagnosticReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.width;
+        writeln!(f, "gamma   {:0width$b} = {}", self.gamma, self.gamma)?;
+        writeln!(f, "epsilon {:0width$b} = {}", self.epsilon, self.epsilon)?;
+
+        for (name, rounds, rating) in [
+            ("oxygen", &self.oxygen_rounds, self.oxygen),
+            ("co2", &self.co2_rounds, self.co2),
+        ] {
+            for round in rounds {
+                writeln!(
+                    f,
+                    "{} bit {}: {} zeros, {} ones, kept {}, {} remaining",
+                    name, round.bit, round.zeros, round.ones, round.kept as u8, round.remaining
+                )?;
+            }
+            writeln!(f, "{} {:0width$b} = {}", name, rating, rating)?;
         }
 
-        Ok(Box::new(gamma * epsilon))
+        Ok(())
     }
+}
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines(input);
-        let len = lines.first().map(String::len).unwrap_or(0);
-        let mut oxygen = lines.clone();
-        let mut co2 = lines;
+/// Report lines packed into integers, most significant bit first
+struct Diagnostic {
+    width: usize,
+    values: Vec<u128>,
+}
 
-        for i in 0..len {
-            let bits = self.count_bits(&oxygen, i);
-            self.retain_at_least_one(&mut oxygen, |line| {
-                (!line.bit_is_set(i) || bits.0 <= bits.1) && (line.bit_is_set(i) || bits.0 > bits.1)
-            });
+impl Diagnostic {
+    fn parse(input: Option<&str>) -> Result<Diagnostic> {
+        let mut width = None;
+        let mut values = solutions::parse_lines(input.unwrap_or_default(), |line| {
+            let line = line.trim();
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(format!("expected {} bits", width.unwrap()));
+            }
+            if line.len() > 128 {
+                return Err("reports are limited to 128 bits".to_string());
+            }
+
+            line.bytes().try_fold(0u128, |value, bit| match bit {
+                b'0' | b'1' => Ok((value << 1) | (bit - b'0') as u128),
+                _ => Err(format!("invalid bit {:?}", bit as char)),
+            })
+        })?;
+
+        let width = width.ok_or_else(|| Problem::input("empty report"))?;
+        values.sort_unstable();
+
+        Ok(Self { width, values })
+    }
+
+    fn mask(&self, bit: usize) -> u128 {
+        1 << (self.width - bit - 1)
+    }
+
+    /// Number of values with the bit unset and set
+    fn count(&self, values: &[u128], bit: usize) -> (usize, usize) {
+        let ones = values.iter().filter(|&&v| v & self.mask(bit) != 0).count();
+        (values.len() - ones, ones)
+    }
 
-            let bits = self.count_bits(&co2, i);
-            self.retain_at_least_one(&mut co2, |line| {
-                (line.bit_is_set(i) || bits.0 <= bits.1) && (!line.bit_is_set(i) || bits.0 > bits.1)
+    /// Applies the bit criteria to the sorted values, the values sharing the bits kept so far
+    /// stay contiguous with zeros before ones, so every round splits the current range in two
+    fn rating(&self, most_common: bool, tie: TieBreak) -> (u128, Vec<FilterRound>) {
+        let mut range = &self.values[..];
+        let mut rounds = vec![];
+
+        for bit in 0..self.width {
+            if range.len() <= 1 {
+                break;
+            }
+
+            let split = range.partition_point(|&v| v & self.mask(bit) == 0);
+            let (zeros, ones) = (split, range.len() - split);
+
+            let kept = match zeros.cmp(&ones) {
+                Ordering::Equal => tie.bit(),
+                ordering => (ordering == Ordering::Less) == most_common,
+            };
+            // An empty side can't be kept, the other one is left untouched
+            let kept = match (zeros, ones) {
+                (0, _) => true,
+                (_, 0) => false,
+                _ => kept,
+            };
+
+            range = if kept {
+                &range[split..]
+            } else {
+                &range[..split]
+            };
+            rounds.push(FilterRound {
+                bit,
+                zeros,
+                ones,
+                kept,
+                remaining: range.len(),
             });
         }
 
-        let oxygen = oxygen
-            .first()
-            .map(|s| isize::from_str_radix(s, 2).unwrap_or(0))
-            .unwrap_or(0);
+        (range[0], rounds)
+    }
 
-        let co2 = co2
-            .first()
-            .map(|s| isize::from_str_radix(s, 2).unwrap_or(0))
-            .unwrap_or(0);
+    fn report(&self, policy: TiePolicy) -> DiagnosticReport {
+        let mut gamma = 0;
+        for bit in 0..self.width {
+            let (zeros, ones) = self.count(&self.values, bit);
+            if ones > zeros || (ones == zeros && policy.gamma.bit()) {
+                gamma |= self.mask(bit);
+            }
+        }
+        let epsilon = !gamma & (u128::MAX >> (128 - self.width));
 
-        Ok(Box::new(oxygen * co2))
+        let (oxygen, oxygen_rounds) = self.rating(true, policy.oxygen);
+        let (co2, co2_rounds) = self.rating(false, policy.co2);
+
+        DiagnosticReport {
+            width: self.width,
+            gamma,
+            epsilon,
+            oxygen,
+            co2,
+            oxygen_rounds,
+            co2_rounds,
+        }
+    }
+}
+
+impl DaySolution {
+    pub fn diagnose(&self, input: Option<&str>, policy: TiePolicy) -> Result<DiagnosticReport> {
+        Ok(Diagnostic::parse(input)?.report(policy))
+    }
+}
+
+impl Solution for DaySolution {
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let report = self.diagnose(input, self.ties)?;
+
+        Ok(Box::new(report.power_consumption()?))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let report = self.diagnose(input, self.ties)?;
+
+        Ok(Box::new(report.life_support()?))
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.ties = match &options.ties {
+            Some(ties) => ties.parse()?,
+            None => TiePolicy::default(),
+        };
+        Ok(())
+    }
+
+    /// Bit counts of every filter round along with the rates and ratings
+    fn analyze(&mut self, reader: &mut dyn BufRead) -> Result<Option<String>> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        Ok(Some(self.diagnose(Some(&input), self.ties)?.to_string()))
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(Diagnostic::parse(Some(input)))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day3::DaySolution;
+    use crate::day3::{DaySolution, TieBreak, TiePolicy};
+    use crate::solutions::{Options, Problem};
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day3_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("198", result.to_string())
     }
@@ -108,10 +286,82 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day3_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("230", result.to_string())
     }
+
+    #[test]
+    fn diagnose() {
+        let input = include_str!("../../inputs/day3_demo.txt");
+        let report = DaySolution::default()
+            .diagnose(Some(input), TiePolicy::default())
+            .unwrap();
+        assert_eq!((report.oxygen, report.co2), (23, 10));
+        assert_eq!(report.oxygen_rounds.len(), 5);
+        assert_eq!(report.co2_rounds.len(), 3);
+        assert!(report
+            .to_string()
+            .contains("co2 bit 2: 1 zeros, 1 ones, kept 0, 1 remaining"));
+
+        let policy = TiePolicy {
+            co2: TieBreak::One,
+            ..Default::default()
+        };
+        let report = DaySolution::default()
+            .diagnose(Some(input), policy)
+            .unwrap();
+        assert_eq!(report.co2, 0b01111);
+
+        let wide = format!(
+            "{}\n{}\n{}\n",
+            "1".repeat(128),
+            "0".repeat(128),
+            "1".repeat(128)
+        );
+        let report = DaySolution::default()
+            .diagnose(Some(&wide), TiePolicy::default())
+            .unwrap();
+        assert_eq!((report.gamma, report.epsilon), (u128::MAX, 0));
+        assert_eq!((report.oxygen, report.co2), (u128::MAX, 0));
+
+        assert_eq!(
+            DaySolution::default()
+                .lint("101\n10\n121\n")
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>(),
+            vec!["line 2: expected 3 bits", "line 3: invalid bit '2'"]
+        );
+        assert_eq!(
+            DaySolution::default().lint(""),
+            vec![Problem::input("empty report")]
+        );
+    }
+
+    #[test]
+    fn ties() {
+        let input = include_str!("../../inputs/day3_demo.txt");
+        assert_eq!("010".parse::<TiePolicy>(), Ok(TiePolicy::default()));
+        assert!("01".parse::<TiePolicy>().is_err());
+        assert!("012".parse::<TiePolicy>().is_err());
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            ties: Some("011".to_string()),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert_eq!(solution.part_2(Some(input)).unwrap().to_string(), "345");
+
+        let report = solution.analyze(&mut input.as_bytes()).unwrap().unwrap();
+        assert!(report.contains("co2 bit 2: 1 zeros, 1 ones, kept 1, 1 remaining"));
+        assert!(report.ends_with("co2 01111 = 15\n"));
+
+        let options = Options {
+            ties: Some("2".to_string()),
+            ..Options::default()
+        };
+        assert!(solution.configure(&options).is_err());
+    }
 }
@@ -1,147 +1,387 @@
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use crate::helpers::{self, Rng};
+use crate::matrix::parallel::{run_all, threads, Task};
+use crate::matrix::Matrix;
+use crate::solutions::{self, Options, Problem, Problems, Result, Solution};
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::io::BufRead;
+use std::ops::Range;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    trials: Option<usize>,
+    seed: Option<u64>,
+}
 
-#[derive(Debug, Default, Clone)]
-struct BoardNum(u8, bool);
-type Board = Vec<BoardNum>;
+type Board = Matrix<u32>;
 
-trait Playable {
-    fn play_and_check(&mut self, value: u8) -> bool;
+/// Completed line of a board, diagonals only count on square boards when enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WinningLine {
+    Row(usize),
+    Column(usize),
+    Diagonal,
+    AntiDiagonal,
 }
 
-impl Playable for Board {
-    fn play_and_check(&mut self, value: u8) -> bool {
-        let mut found_index = None;
-        for (index, mut num) in self.iter_mut().enumerate() {
-            if value == num.0 {
-                num.1 = true;
-                found_index = Some(index);
-                break;
-            }
-        }
-
-        let index = match found_index {
-            None => return false,
-            Some(index) => index,
-        };
+/// Board `board` wins on draw `turn` (0-based) when `number` completes `line`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Win {
+    pub board: usize,
+    pub turn: usize,
+    pub number: u32,
+    pub line: WinningLine,
+    pub score: u64,
+}
 
-        let row = index / 5;
-        let col = index % 5;
+/// Boards in the order they win, boards that never win are listed separately
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ranking {
+    pub wins: Vec<Win>,
+    pub never_won: Vec<usize>,
+}
 
-        (0..5).filter(|&i| self[i + row * 5].1).count() == 5
-            || (0..5).filter(|&i| self[col + i * 5].1).count() == 5
-    }
+/// Marks of one board during a game
+#[derive(Clone)]
+struct BoardState {
+    marked: Vec<bool>,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    diagonal: usize,
+    anti_diagonal: usize,
+    unmarked: u64,
+    won: bool,
 }
 
-impl DaySolution {
-    fn parse(&self, lines: Vec<String>) -> (Vec<u8>, Vec<Board>) {
-        let mut boards = vec![];
-        let nums = lines
-            .first()
-            .unwrap()
-            .split(',')
-            .map(|s| s.parse().unwrap())
-            .collect();
+/// Bingo over boards of any size, with an index from every number to its board positions
+pub struct Bingo<'a> {
+    boards: &'a [Board],
+    diagonals: bool,
+    index: HashMap<u32, Vec<(usize, usize, usize)>>,
+}
 
-        let mut board = Board::default();
-        for line in lines.iter().skip(2) {
-            if line.is_empty() {
-                boards.push(board.clone());
-                board.clear();
-                continue;
+impl<'a> Bingo<'a> {
+    pub fn new(boards: &'a [Board], diagonals: bool) -> Bingo<'a> {
+        let mut index: HashMap<u32, Vec<_>> = HashMap::new();
+        for (i, board) in boards.iter().enumerate() {
+            for (&number, (x, y)) in board.iter() {
+                index.entry(number).or_default().push((i, x, y));
             }
+        }
+
+        Self {
+            boards,
+            diagonals,
+            index,
+        }
+    }
+
+    fn start(&self) -> Vec<BoardState> {
+        self.boards
+            .iter()
+            .map(|board| BoardState {
+                marked: vec![false; board.size()],
+                rows: vec![0; board.height],
+                columns: vec![0; board.width],
+                diagonal: 0,
+                anti_diagonal: 0,
+                unmarked: board.iter().map(|(&number, _)| number as u64).sum(),
+                won: false,
+            })
+            .collect()
+    }
+
+    /// Marks `(x, y)` of board `i`, returns the line it completes
+    fn mark(&self, state: &mut BoardState, i: usize, x: usize, y: usize) -> Option<WinningLine> {
+        let board = &self.boards[i];
+        if std::mem::replace(&mut state.marked[y * board.width + x], true) {
+            return None;
+        }
 
-            let row_nums = line
-                .split(' ')
-                .flat_map(|s| s.parse::<u8>().ok())
-                .map(|v| BoardNum(v, false))
-                .collect::<Vec<BoardNum>>();
+        state.unmarked -= *board.get(x, y).unwrap() as u64;
+        state.rows[y] += 1;
+        state.columns[x] += 1;
 
-            board.extend(row_nums)
+        let square = self.diagonals && board.width == board.height;
+        if square && x == y {
+            state.diagonal += 1;
+        }
+        if square && x + y + 1 == board.width {
+            state.anti_diagonal += 1;
         }
-        boards.push(board);
 
-        (nums, boards)
+        if state.rows[y] == board.width {
+            Some(WinningLine::Row(y))
+        } else if state.columns[x] == board.height {
+            Some(WinningLine::Column(x))
+        } else if square && x == y && state.diagonal == board.width {
+            Some(WinningLine::Diagonal)
+        } else if square && x + y + 1 == board.width && state.anti_diagonal == board.width {
+            Some(WinningLine::AntiDiagonal)
+        } else {
+            None
+        }
     }
-}
 
-impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines(input);
-        let (nums, mut boards) = self.parse(lines);
+    /// Plays every draw, calling `on_win` for each board the first time it wins,
+    /// stops early once all the boards won or a score doesn't fit
+    pub(crate) fn play<F: FnMut(Win)>(&self, draws: &[u32], mut on_win: F) -> Result<()> {
+        let mut states = self.start();
+        let mut remaining = states.len();
+
+        for (turn, &number) in draws.iter().enumerate() {
+            for &(i, x, y) in self.index.get(&number).into_iter().flatten() {
+                let state = &mut states[i];
+                if state.won {
+                    continue;
+                }
 
-        let mut winner: Option<(Board, u8)> = None;
+                if let Some(line) = self.mark(state, i, x, y) {
+                    let score = state
+                        .unmarked
+                        .checked_mul(number as u64)
+                        .ok_or_else(|| format!("the score of board {} overflows", i))?;
 
-        for num in nums {
-            for board in boards.iter_mut() {
-                if board.play_and_check(num) {
-                    winner = Some((board.to_vec(), num));
-                    break;
+                    state.won = true;
+                    remaining -= 1;
+                    on_win(Win {
+                        board: i,
+                        turn,
+                        number,
+                        line,
+                        score,
+                    });
                 }
             }
 
-            if winner.is_some() {
+            if remaining == 0 {
                 break;
             }
         }
 
-        let (winner, num) = winner.unwrap();
-        let sum = winner
-            .into_iter()
-            .filter(|num| !num.1)
-            .map(|num| num.0 as u32)
-            .sum::<u32>();
+        Ok(())
+    }
+
+    pub fn rank(&self, draws: &[u32]) -> Result<Ranking> {
+        let mut wins = vec![];
+        self.play(draws, |win| wins.push(win))?;
+        wins.sort_by_key(|win| (win.turn, win.board));
+
+        let never_won = (0..self.boards.len())
+            .filter(|&i| wins.iter().all(|win| win.board != i))
+            .collect();
+
+        Ok(Ranking { wins, never_won })
+    }
+}
 
-        Ok(Box::new(sum * num as u32))
+/// Outcome of a board over random draw orders, boards winning on the same draw win in index
+/// order like in the ranking: the lower index counts as first and the higher one as last
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardOdds {
+    pub board: usize,
+    pub first: f64,
+    pub last: f64,
+    /// Mean number of draws until the board wins, over the trials where it wins
+    pub expected_draws: Option<f64>,
+}
+
+impl Display for BoardOdds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "board {}: first {:.2}%, last {:.2}%, ",
+            self.board,
+            self.first * 100.0,
+            self.last * 100.0
+        )?;
+        match self.expected_draws {
+            Some(draws) => writeln!(f, "expected draws {:.2}", draws),
+            None => writeln!(f, "never wins"),
+        }
     }
+}
+
+/// Per board counts of first wins, last wins, wins and total draws until winning
+#[derive(Clone, Copy, Default)]
+struct Tally {
+    first: usize,
+    last: usize,
+    wins: usize,
+    draws: usize,
+}
+
+impl DaySolution {
+    /// Plays `trials` games with the drawn numbers of the input shuffled, trial `i` uses
+    /// its own generator seeded from `seed` and `i` so the odds don't depend on the threads
+    pub fn analyze(&self, input: Option<&str>, trials: usize, seed: u64) -> Result<Vec<BoardOdds>> {
+        if trials == 0 {
+            return Err("at least one trial is needed".into());
+        }
+
+        let (nums, boards) = self.parse(input.unwrap_or_default())?;
+        let bingo = Bingo::new(&boards, false);
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines(input);
-        let (nums, mut boards) = self.parse(lines);
+        let play = |trials: Range<usize>| {
+            let mut tally = vec![Tally::default(); boards.len()];
+            let mut draws = nums.clone();
 
-        let mut winners = vec![];
-        let mut winners_idx = vec![];
+            for trial in trials {
+                draws.copy_from_slice(&nums);
+                Rng::new(seed ^ (trial as u64).wrapping_mul(0x9e3779b97f4a7c15))
+                    .shuffle(&mut draws);
 
-        let total_boards = boards.len();
-        for num in nums {
-            for (index, board) in boards.iter_mut().enumerate() {
-                if !winners_idx.contains(&index) && board.play_and_check(num) {
-                    winners_idx.push(index);
-                    winners.push((board.to_vec(), num));
+                let mut last = None;
+                bingo
+                    .play(&draws, |win| {
+                        if last.is_none() {
+                            tally[win.board].first += 1;
+                        }
+                        tally[win.board].wins += 1;
+                        tally[win.board].draws += win.turn + 1;
+                        last = Some(win.board);
+                    })
+                    .map_err(|error| error.to_string())?;
+                if let Some(board) = last {
+                    tally[board].last += 1;
                 }
             }
 
-            if winners.len() == total_boards {
-                break;
+            Ok(tally)
+        };
+
+        let play = &play;
+        let chunk = trials.div_ceil(threads()).max(1);
+        let mut tallies = (0..trials)
+            .step_by(chunk)
+            .map(|_| Ok(vec![]))
+            .collect::<Vec<_>>();
+        run_all(
+            tallies
+                .iter_mut()
+                .enumerate()
+                .map(|(i, tally)| {
+                    let start = i * chunk;
+                    Box::new(move || *tally = play(start..(start + chunk).min(trials))) as Task<'_>
+                })
+                .collect(),
+        );
+        let tallies = tallies
+            .into_iter()
+            .collect::<core::result::Result<Vec<_>, String>>()?;
+
+        Ok((0..boards.len())
+            .map(|board| {
+                let tally = tallies.iter().fold(Tally::default(), |sum, tally| Tally {
+                    first: sum.first + tally[board].first,
+                    last: sum.last + tally[board].last,
+                    wins: sum.wins + tally[board].wins,
+                    draws: sum.draws + tally[board].draws,
+                });
+
+                BoardOdds {
+                    board,
+                    first: tally.first as f64 / trials as f64,
+                    last: tally.last as f64 / trials as f64,
+                    expected_draws: (tally.wins > 0)
+                        .then(|| tally.draws as f64 / tally.wins as f64),
+                }
+            })
+            .collect())
+    }
+
+    /// Drawn numbers on the first line, then boards separated by empty lines
+    fn parse(&self, input: &str) -> Result<(Vec<u32>, Vec<Board>)> {
+        let blocks = helpers::blocks(input);
+        let (start, numbers) = blocks
+            .first()
+            .ok_or_else(|| Problem::input("empty input"))?;
+        let mut problems = vec![];
+
+        let nums = numbers
+            .join(",")
+            .split(',')
+            .map(|n| n.trim().parse())
+            .collect::<core::result::Result<_, _>>()
+            .unwrap_or_else(|_| {
+                problems.push(Problem::new(
+                    *start,
+                    "expected comma-separated drawn numbers",
+                ));
+                vec![]
+            });
+
+        let mut boards = vec![];
+        for (start, rows) in &blocks[1..] {
+            match Matrix::<u32>::parse_separated(&rows.join("\n"), None) {
+                Ok(board) => boards.push(board),
+                Err(error) => problems.extend(
+                    solutions::problems(error.into())
+                        .into_iter()
+                        .map(|problem| problem.within(*start)),
+                ),
             }
         }
 
-        let (winner, num) = winners.last().unwrap();
-        let sum: u32 = winner
-            .iter()
-            .filter(|num| !num.1)
-            .map(|num| num.0 as u32)
-            .sum();
+        Problems::result(problems, (nums, boards))
+    }
+
+    fn ranking(&self, input: Option<&str>) -> Result<Ranking> {
+        let (nums, boards) = self.parse(input.unwrap_or_default())?;
+        let ranking = Bingo::new(&boards, false).rank(&nums)?;
+
+        match ranking.wins.is_empty() {
+            true => Err("no board wins".into()),
+            false => Ok(ranking),
+        }
+    }
+}
 
-        Ok(Box::new(sum * (*num as u32)))
+impl Solution for DaySolution {
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let ranking = self.ranking(input)?;
+
+        Ok(Box::new(ranking.wins.first().unwrap().score))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let ranking = self.ranking(input)?;
+
+        Ok(Box::new(ranking.wins.last().unwrap().score))
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.trials = options.trials;
+        self.seed = options.seed;
+        Ok(())
+    }
+
+    fn analyze(&mut self, reader: &mut dyn BufRead) -> Result<Option<String>> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        let (trials, seed) = (self.trials.unwrap_or(10000), self.seed.unwrap_or(0));
+        let odds = DaySolution::analyze(self, Some(&input), trials, seed)?;
+
+        Ok(Some(odds.iter().map(|board| board.to_string()).collect()))
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(input))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day4::DaySolution;
+    use crate::day4::{Bingo, DaySolution, WinningLine};
+    use crate::matrix::Matrix;
+    use crate::solutions::Options;
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day4_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("4512", result.to_string())
     }
@@ -149,10 +389,94 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day4_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("1924", result.to_string())
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day4_demo.txt");
+        assert_eq!(DaySolution::default().lint(input), vec![]);
+
+        let input = "1,2,x\n\n1 2\n3 4\n\n1 2 3\n4 5 6\n7 8";
+        let lines = DaySolution::default()
+            .lint(input)
+            .iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![1, 8]);
+    }
+
+    #[test]
+    fn rank() {
+        let input = include_str!("../../inputs/day4_demo.txt");
+        let (nums, boards) = DaySolution::default().parse(input).unwrap();
+        let ranking = Bingo::new(&boards, false).rank(&nums).unwrap();
+
+        let order = ranking.wins.iter().map(|win| win.board).collect::<Vec<_>>();
+        assert_eq!(order, vec![2, 0, 1]);
+        assert_eq!(ranking.wins[0].turn, 11);
+        assert_eq!(ranking.wins[0].line, WinningLine::Row(0));
+        assert_eq!(ranking.wins[2].score, 1924);
+
+        let boards = vec![
+            Matrix::<u32>::parse_separated("1 2 3\n7 5 6", None).unwrap(),
+            Matrix::<u32>::parse_separated("1 2\n3 4", None).unwrap(),
+        ];
+        let ranking = Bingo::new(&boards, true).rank(&[4, 1, 7, 5, 6]).unwrap();
+        assert_eq!(ranking.wins[0].board, 1);
+        assert_eq!(ranking.wins[0].line, WinningLine::Diagonal);
+        assert_eq!(ranking.wins[0].score, 5);
+        assert_eq!(ranking.wins[1].line, WinningLine::Column(0));
+        assert_eq!(ranking.wins[1].score, 112);
+        assert!(ranking.never_won.is_empty());
+
+        // 4 unmarked numbers near u32::MAX times the last one drawn don't fit a u64
+        let board = "4294967295 4294967294\n4294967293 4294967292\n4294967291 4294967290";
+        let boards = vec![Matrix::<u32>::parse_separated(board, None).unwrap()];
+        let ranking = Bingo::new(&boards, false).rank(&[u32::MAX, u32::MAX - 1]);
+        assert!(ranking.is_err());
+    }
+
+    #[test]
+    fn analyze() {
+        let input = include_str!("../../inputs/day4_demo.txt");
+        let odds = DaySolution::default()
+            .analyze(Some(input), 2000, 7)
+            .unwrap();
+
+        assert_eq!(
+            odds,
+            DaySolution::default()
+                .analyze(Some(input), 2000, 7)
+                .unwrap()
+        );
+        assert!((odds.iter().map(|board| board.first).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((odds.iter().map(|board| board.last).sum::<f64>() - 1.0).abs() < 1e-9);
+        // Every board completes a line before all 27 numbers are drawn
+        assert!(odds
+            .iter()
+            .all(|board| board.expected_draws.unwrap() < 27.0));
+
+        // Both boards always win on the same draw, like in the ranking the second one is last
+        let odds = DaySolution::default()
+            .analyze(Some("1,2,3,4\n\n1 2\n3 4\n\n2 1\n4 3"), 100, 7)
+            .unwrap();
+        assert_eq!((odds[0].first, odds[0].last), (1.0, 0.0));
+        assert_eq!((odds[1].first, odds[1].last), (0.0, 1.0));
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            trials: Some(100),
+            seed: Some(7),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let report = Solution::analyze(&mut solution, &mut input.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.lines().count(), 3);
+        assert!(report.starts_with("board 0: first "));
+    }
 }
@@ -3,22 +3,23 @@ use std::fmt::{Debug, Display};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use itertools::Itertools;
+
+use crate::matrix::image::{gray, Rgb};
+use crate::matrix::Matrix;
+use crate::solutions::{self, Options, Problem, Result, Solution};
+
+/// Largest vent field rendered as a heat map
+const MAX_HEAT_MAP_CELLS: usize = 100_000_000;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    threshold: Option<usize>,
+}
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
 struct Point(i32, i32);
 
-impl Point {
-    fn move_by(&mut self, offset: (i32, i32)) {
-        self.0 += offset.0;
-        self.1 += offset.1;
-    }
-}
-
 impl FromStr for Point {
     type Err = ParseIntError;
 
@@ -34,17 +35,34 @@ struct Segment {
 }
 
 impl Segment {
-    fn direction(&self) -> (i32, i32) {
-        match (self.start.0, self.start.1, self.end.0, self.end.1) {
-            (x1, y1, x2, y2) if x1 == x2 && y1 < y2 => (0, 1),
-            (x1, y1, x2, y2) if x1 == x2 && y1 > y2 => (0, -1),
-            (x1, y1, x2, y2) if y1 == y2 && x1 < x2 => (1, 0),
-            (x1, y1, x2, y2) if y1 == y2 && x1 > x2 => (-1, 0),
-            (x1, y1, x2, y2) if x1 < x2 && y1 < y2 => (1, 1),
-            (x1, y1, x2, y2) if x1 > x2 && y1 < y2 => (-1, 1),
-            (x1, y1, x2, y2) if x1 < x2 && y1 > y2 => (1, -1),
-            _ => (-1, -1),
-        }
+    /// Points of the segment from start to end with Bresenham's algorithm, which gives
+    /// every point of horizontal, vertical and diagonal segments
+    fn points(&self) -> impl Iterator<Item = Point> {
+        let (start, end) = (self.start, self.end);
+        let dx = (end.0 as i64 - start.0 as i64).abs();
+        let dy = -(end.1 as i64 - start.1 as i64).abs();
+        let (sx, sy) = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+
+        let mut error = dx + dy;
+        let mut next = Some(start);
+        std::iter::from_fn(move || {
+            let point = next?;
+            next = (point != end).then(|| {
+                let doubled = 2 * error;
+                let mut step = point;
+                if doubled >= dy {
+                    error += dy;
+                    step.0 += sx;
+                }
+                if doubled <= dx {
+                    error += dx;
+                    step.1 += sy;
+                }
+                step
+            });
+
+            Some(point)
+        })
     }
 }
 
@@ -60,51 +78,369 @@ impl FromStr for Segment {
     }
 }
 
+/// Direction of the lines segments lie on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Family {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    AntiDiagonal,
+}
+
+impl Family {
+    const ALL: [Family; 4] = [
+        Family::Horizontal,
+        Family::Vertical,
+        Family::Diagonal,
+        Family::AntiDiagonal,
+    ];
+
+    /// Key of the line of the family through `point`
+    fn key(self, (x, y): (i64, i64)) -> i64 {
+        match self {
+            Family::Horizontal => y,
+            Family::Vertical => x,
+            Family::Diagonal => x - y,
+            Family::AntiDiagonal => x + y,
+        }
+    }
+}
+
+/// Infinite line through a segment: `y = key`, `x = key`, `x - y = key` or `x + y = key`.
+/// Lattice points on it are numbered by `x`, or by `y` for vertical lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Line {
+    family: Family,
+    key: i64,
+}
+
+impl Line {
+    fn param(&self, (x, y): (i64, i64)) -> i64 {
+        match self.family {
+            Family::Vertical => y,
+            _ => x,
+        }
+    }
+
+    /// Lattice point numbered `param`
+    fn point(&self, param: i64) -> (i64, i64) {
+        match self.family {
+            Family::Horizontal => (param, self.key),
+            Family::Vertical => (self.key, param),
+            Family::Diagonal => (param, param - self.key),
+            Family::AntiDiagonal => (param, self.key - param),
+        }
+    }
+
+    /// Lattice point shared with a line of another family
+    fn intersection(&self, other: &Line) -> Option<(i64, i64)> {
+        let (a, b) = match self.family < other.family {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        match (a.family, b.family) {
+            (Family::Horizontal, Family::Vertical) => Some((b.key, a.key)),
+            (Family::Horizontal, Family::Diagonal) => Some((a.key + b.key, a.key)),
+            (Family::Horizontal, Family::AntiDiagonal) => Some((b.key - a.key, a.key)),
+            (Family::Vertical, Family::Diagonal) => Some((a.key, a.key - b.key)),
+            (Family::Vertical, Family::AntiDiagonal) => Some((a.key, b.key - a.key)),
+            (Family::Diagonal, Family::AntiDiagonal) if (a.key + b.key) % 2 == 0 => {
+                let x = (a.key + b.key) / 2;
+                Some((x, x - a.key))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Segment {
+    /// Line of the segment along with its first and last parameter
+    fn line(&self) -> Result<(Line, i64, i64)> {
+        let (x1, y1, x2, y2) = (
+            self.start.0 as i64,
+            self.start.1 as i64,
+            self.end.0 as i64,
+            self.end.1 as i64,
+        );
+
+        let (family, key) = match (x2 - x1, y2 - y1) {
+            (_, 0) => (Family::Horizontal, y1),
+            (0, _) => (Family::Vertical, x1),
+            (dx, dy) if dx == dy => (Family::Diagonal, x1 - y1),
+            (dx, dy) if dx == -dy => (Family::AntiDiagonal, x1 + y1),
+            _ => {
+                return Err(
+                    format!("unsupported segment {:?} -> {:?}", self.start, self.end).into(),
+                )
+            }
+        };
+
+        let line = Line { family, key };
+        let (a, b) = (line.param((x1, y1)), line.param((x2, y2)));
+
+        Ok((line, a.min(b), a.max(b)))
+    }
+}
+
+/// Run of lattice points `from..=to` of a line covered by `coverage` segments
+struct Piece {
+    line: Line,
+    from: i64,
+    to: i64,
+    coverage: usize,
+}
+
+/// Covered runs of every line segments lie on, from a sweep over the segment ends
+struct Coverage {
+    lines: HashMap<Line, Vec<Piece>>,
+}
+
+impl Coverage {
+    /// Only horizontal, vertical and diagonal segments are supported
+    fn new<'a>(segments: impl Iterator<Item = &'a Segment>) -> Result<Coverage> {
+        let mut events: HashMap<Line, Vec<(i64, i32)>> = HashMap::new();
+        for segment in segments {
+            let (line, from, to) = segment.line()?;
+            events
+                .entry(line)
+                .or_default()
+                .extend([(from, 1), (to + 1, -1)]);
+        }
+
+        let mut lines = HashMap::new();
+        for (line, mut events) in events {
+            events.sort_unstable();
+
+            let mut pieces = vec![];
+            let mut coverage = 0;
+            for (i, &(at, delta)) in events.iter().enumerate() {
+                coverage += delta;
+                match events.get(i + 1) {
+                    Some(&(next, _)) if coverage > 0 && next > at => pieces.push(Piece {
+                        line,
+                        from: at,
+                        to: next - 1,
+                        coverage: coverage as usize,
+                    }),
+                    _ => {}
+                }
+            }
+            lines.insert(line, pieces);
+        }
+
+        Ok(Coverage { lines })
+    }
+
+    /// Covered run of `line` containing the point numbered `param`
+    fn piece(&self, line: &Line, param: i64) -> Option<&Piece> {
+        let pieces = self.lines.get(line)?;
+        let piece = pieces.get(pieces.partition_point(|piece| piece.to < param))?;
+        (piece.from <= param).then_some(piece)
+    }
+
+    /// Segments covering `point`, one run of each family at most
+    fn at(&self, point: (i64, i64)) -> usize {
+        Family::ALL
+            .into_iter()
+            .filter_map(|family| {
+                let line = Line {
+                    family,
+                    key: family.key(point),
+                };
+                self.piece(&line, line.param(point))
+            })
+            .map(|piece| piece.coverage)
+            .sum()
+    }
+
+    /// Counts the points covered by at least `threshold` segments without visiting them:
+    /// overlaps along a line come from the runs. For crossings, the lines of every family
+    /// are sorted by key: a run only meets the lines of another family whose keys lie
+    /// between the ones through its ends, so only those are looked up.
+    fn count(&self, threshold: usize) -> usize {
+        let mut count = self
+            .lines
+            .values()
+            .flatten()
+            .filter(|piece| piece.coverage >= threshold)
+            .map(|piece| (piece.to - piece.from + 1) as usize)
+            .sum::<usize>();
+
+        let mut keys: HashMap<Family, Vec<i64>> = HashMap::new();
+        for line in self.lines.keys() {
+            keys.entry(line.family).or_default().push(line.key);
+        }
+        keys.values_mut().for_each(|keys| keys.sort_unstable());
+
+        // Lines crossing at each point along with their coverage there
+        let mut crossings: HashMap<(i64, i64), HashMap<Line, usize>> = HashMap::new();
+        for a in self.lines.values().flatten() {
+            // Every pair of families is swept once, from the run of the first one
+            for (&family, keys) in keys.iter().filter(|(&f, _)| f > a.line.family) {
+                let ends = [a.from, a.to].map(|param| family.key(a.line.point(param)));
+                let (low, high) = (ends[0].min(ends[1]), ends[0].max(ends[1]));
+
+                let start = keys.partition_point(|&key| key < low);
+                for &key in keys[start..].iter().take_while(|&&key| key <= high) {
+                    let line = Line { family, key };
+                    let point = match a.line.intersection(&line) {
+                        Some(point) => point,
+                        None => continue,
+                    };
+
+                    if let Some(b) = self.piece(&line, line.param(point)) {
+                        let lines = crossings.entry(point).or_default();
+                        lines.insert(a.line, a.coverage);
+                        lines.insert(b.line, b.coverage);
+                    }
+                }
+            }
+        }
+
+        // Crossings count once, including those already counted as overlaps along their lines
+        for lines in crossings.values() {
+            count += (lines.values().sum::<usize>() >= threshold) as usize;
+            count -= lines
+                .values()
+                .filter(|&&coverage| coverage >= threshold)
+                .count();
+        }
+
+        count
+    }
+}
+
 impl DaySolution {
-    fn solve(&self, input: Option<String>, predicate: Option<fn(&Segment) -> bool>) -> usize {
-        let mut map: HashMap<Point, i32> = HashMap::new();
-        let segments = parse_lines::<Segment>(input);
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Segment>> {
+        solutions::parse_lines(input.unwrap_or_default(), |line| {
+            line.trim()
+                .parse()
+                .map_err(|_| format!("expected a segment `x1,y1 -> x2,y2`, found {:?}", line))
+        })
+    }
+
+    /// Segments that have to overlap at a point, 2 unless configured
+    fn threshold(&self) -> Result<usize> {
+        match self.threshold.unwrap_or(2) {
+            0 => Err("the threshold must be at least 1".into()),
+            threshold => Ok(threshold),
+        }
+    }
+
+    /// Counts the points covered by at least `threshold` segments from the geometry, only
+    /// horizontal, vertical and diagonal segments are supported
+    fn count_overlaps(
+        &self,
+        input: Option<&str>,
+        predicate: Option<fn(&Segment) -> bool>,
+        threshold: usize,
+    ) -> Result<usize> {
         let predicate = predicate.unwrap_or(|_| true);
+        let segments = self.parse(input)?;
+
+        Ok(Coverage::new(segments.iter().filter(|segment| predicate(segment)))?.count(threshold))
+    }
 
-        segments.into_iter().filter(predicate).for_each(|s| {
-            let mut start = s.start;
-            let offset = s.direction();
+    /// Points covered by at least `threshold` segments of any angle. Horizontal, vertical and
+    /// diagonal segments are counted from the geometry, only the points of the other ones are
+    /// visited to correct the count there.
+    pub fn overlaps(&self, input: Option<&str>, threshold: usize) -> Result<usize> {
+        let (supported, others): (Vec<_>, Vec<_>) = self
+            .parse(input)?
+            .into_iter()
+            .partition(|segment| segment.line().is_ok());
 
-            while start != s.end {
-                *map.entry(start).or_insert(0) += 1;
-                start.move_by(offset);
-            }
-            *map.entry(start).or_insert(0) += 1;
-        });
+        let coverage = Coverage::new(supported.iter())?;
+        let mut extra: HashMap<Point, usize> = HashMap::new();
+        for point in others.iter().flat_map(|segment| segment.points()) {
+            *extra.entry(point).or_default() += 1;
+        }
 
-        map.values().filter(|&&count| count > 1).count()
+        let mut count = coverage.count(threshold);
+        for (point, extra) in extra {
+            let covered = coverage.at((point.0 as i64, point.1 as i64));
+            count += (covered + extra >= threshold) as usize;
+            count -= (covered >= threshold) as usize;
+        }
+
+        Ok(count)
+    }
+
+    /// Number of segments covering every point of the bounding box of the segments,
+    /// along with the position of its top left corner
+    pub fn heat_map(&self, input: Option<&str>) -> Result<(Matrix<u32>, (i32, i32))> {
+        let segments = self.parse(input)?;
+        let points = segments.iter().flat_map(|s| [s.start, s.end]);
+        let (min_x, max_x) = points
+            .clone()
+            .map(|p| p.0)
+            .minmax()
+            .into_option()
+            .ok_or("no segments")?;
+        let (min_y, max_y) = points.map(|p| p.1).minmax().into_option().unwrap();
+
+        let (width, height) = ((max_x - min_x) as usize + 1, (max_y - min_y) as usize + 1);
+        if width.saturating_mul(height) > MAX_HEAT_MAP_CELLS {
+            return Err(format!(
+                "a {}x{} vent field is too large for a heat map",
+                width, height
+            )
+            .into());
+        }
+
+        let mut heat = Matrix::new(width, height);
+        for point in segments.iter().flat_map(|segment| segment.points()) {
+            let (x, y) = ((point.0 - min_x) as usize, (point.1 - min_y) as usize);
+            heat.set(x, y, heat.get(x, y).unwrap() + 1);
+        }
+
+        Ok((heat, (min_x, min_y)))
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.count_overlaps(
             input,
             Some(|s| s.start.0 == s.end.0 || s.start.1 == s.end.1),
-        )))
+            self.threshold()?,
+        )?))
+    }
+
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.overlaps(input, self.threshold()?)?))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, None)))
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        self.threshold = options.threshold;
+        Ok(())
+    }
+
+    fn render(&mut self, input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        let (heat, _) = self.heat_map(input)?;
+        let max = heat.iter().map(|(&count, _)| count).max().unwrap_or(0);
+
+        Ok(Some(heat.map(|&count| gray(count as usize, max as usize))))
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day5::DaySolution;
+    use crate::helpers::Rng;
+    use crate::solutions::{Options, Problem};
     use crate::Solution;
+    use itertools::Itertools;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day5_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("5", result.to_string())
     }
@@ -112,10 +448,133 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day5_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("12", result.to_string())
     }
+
+    #[test]
+    fn count_overlaps() {
+        let input = include_str!("../../inputs/day5_demo.txt");
+        let crossing =
+            "0,0 -> 4,4\n0,4 -> 4,0\n2,0 -> 2,4\n0,2 -> 4,2\n1,3 -> 3,3\n3,3 -> 3,0\n0,3 -> 3,0";
+        for input in [input, crossing] {
+            for threshold in 1..=3 {
+                let (heat, _) = DaySolution::default().heat_map(Some(input)).unwrap();
+                assert_eq!(
+                    DaySolution::default()
+                        .count_overlaps(Some(input), None, threshold)
+                        .unwrap(),
+                    heat.iter()
+                        .filter(|(&count, _)| count as usize >= threshold)
+                        .count()
+                );
+            }
+        }
+
+        let far = "0,0 -> 4000000,4000000\n0,4000000 -> 4000000,0\n1000000,0 -> 3000000,0\n2000000,0 -> 2000000,4000000";
+        assert_eq!(
+            DaySolution::default()
+                .count_overlaps(Some(far), None, 2)
+                .unwrap(),
+            2
+        );
+
+        assert!(DaySolution::default()
+            .count_overlaps(Some("0,0 -> 2,1"), None, 2)
+            .is_err());
+        assert_eq!(
+            DaySolution::default().lint("0,0 -> 2,2\n0,0 > 1,1\n\n1,x -> 2,2\n"),
+            vec![
+                Problem::new(
+                    2,
+                    "expected a segment `x1,y1 -> x2,y2`, found \"0,0 > 1,1\""
+                ),
+                Problem::new(
+                    4,
+                    "expected a segment `x1,y1 -> x2,y2`, found \"1,x -> 2,2\""
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn count_many() {
+        // Enough segments for a quadratic number of crossing checks to show
+        let mut rng = Rng::new(5);
+        let segments = (0..20_000)
+            .map(|_| {
+                let (x, y) = (rng.below(2000) as i32, rng.below(2000) as i32);
+                let length = rng.below(20) as i32;
+                let (dx, dy) = [(1, 0), (0, 1), (1, 1), (1, -1)][rng.below(4)];
+                format!("{},{} -> {},{}", x, y, x + dx * length, y + dy * length)
+            })
+            .join("\n");
+
+        let (heat, _) = DaySolution::default().heat_map(Some(&segments)).unwrap();
+        for threshold in 2..=3 {
+            assert_eq!(
+                DaySolution::default()
+                    .count_overlaps(Some(&segments), None, threshold)
+                    .unwrap(),
+                heat.iter()
+                    .filter(|(&count, _)| count as usize >= threshold)
+                    .count()
+            );
+        }
+    }
+
+    #[test]
+    fn heat_map() {
+        let input = include_str!("../../inputs/day5_demo.txt");
+        let (heat, origin) = DaySolution::default().heat_map(Some(input)).unwrap();
+        let rendered = heat.render_to_string(|count| match count {
+            Some(0) | None => ".".to_string(),
+            Some(count) => count.to_string(),
+        });
+
+        assert_eq!(origin, (0, 0));
+        assert_eq!(
+            rendered,
+            "1.1....11.\n\
+             .111...2..\n\
+             ..2.1.111.\n\
+             ...1.2.2..\n\
+             .112313211\n\
+             ...1.2....\n\
+             ..1...1...\n\
+             .1.....1..\n\
+             1.......1.\n\
+             222111...."
+        );
+
+        // Bresenham points of 0,0 -> 4,2 are 0,0 1,1 2,1 3,2 4,2
+        let input = "0,0 -> 4,2\n0,1 -> 4,1\n4,2 -> 4,2";
+        assert_eq!(DaySolution::default().overlaps(Some(input), 2).unwrap(), 3);
+        assert_eq!(DaySolution::default().overlaps(Some(input), 3).unwrap(), 0);
+
+        // Off-angle segments merged with the geometric count of the others
+        let input =
+            "0,0 -> 4,2\n0,1 -> 4,1\n4,2 -> 4,2\n0,0 -> 4,4\n0,4 -> 4,0\n2,0 -> 2,4\n1,0 -> 7,3";
+        let (heat, _) = DaySolution::default().heat_map(Some(input)).unwrap();
+        for threshold in 1..=4 {
+            assert_eq!(
+                DaySolution::default()
+                    .overlaps(Some(input), threshold)
+                    .unwrap(),
+                heat.iter()
+                    .filter(|(&count, _)| count as usize >= threshold)
+                    .count()
+            );
+        }
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            threshold: Some(3),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let input = include_str!("../../inputs/day5_demo.txt");
+        assert_eq!(solution.part_2(Some(input)).unwrap().to_string(), "2");
+    }
 }
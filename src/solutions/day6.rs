@@ -1,51 +1,462 @@
-use crate::solutions::{Result, Solution};
+use crate::bigint::BigUint;
+use crate::matrix::Matrix;
+use crate::solutions::{self, Options, Problem, Result, Solution, TraceFormat};
+use itertools::Itertools;
+use json::object;
 use std::fmt::Display;
+use std::iter::once;
 
 #[derive(Default)]
-pub struct DaySolution;
+pub struct DaySolution {
+    life_cycle: LifeCycle,
+    days: Option<u64>,
+    modulus: Option<Modular>,
+    exact: bool,
+}
+
+/// Number type the population is counted with
+pub trait Arithmetic {
+    type Value: Clone + Display;
+
+    fn value(&self, n: u64) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Result<Self::Value>;
+    fn mul(&self, a: &Self::Value, b: &Self::Value) -> Result<Self::Value>;
+}
+
+/// Exact `u64` counts, overflowing any intermediate count is an error
+pub struct Checked;
+
+/// `u128` counts stopping at its maximum: every count below it is exact, so the counts that
+/// fit in `u64` are, whatever the intermediate ones
+struct Saturating;
+
+/// Counts modulo the given number
+pub struct Modular(u64);
+
+/// Most states the transition matrix is built for, its multiplications take cubic time
+const MAX_STATES: usize = 256;
+
+/// Most states of a life cycle, one count is kept for each of them
+const MAX_LIFE_CYCLE_STATES: usize = 1_000_000;
+
+/// Most state updates of a day by day simulation
+const MAX_UPDATES: u64 = 10_000_000;
+
+/// Exact counts of any size, the number of digits grows by about one every 27 days
+pub struct Exact;
+
+impl Arithmetic for Checked {
+    type Value = u64;
+
+    fn value(&self, n: u64) -> u64 {
+        n
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64> {
+        Ok(a.checked_add(*b).ok_or("population overflows u64")?)
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> Result<u64> {
+        Ok(a.checked_mul(*b).ok_or("population overflows u64")?)
+    }
+}
+
+impl Arithmetic for Saturating {
+    type Value = u128;
+
+    fn value(&self, n: u64) -> u128 {
+        n as u128
+    }
+
+    fn add(&self, a: &u128, b: &u128) -> Result<u128> {
+        Ok(a.saturating_add(*b))
+    }
+
+    fn mul(&self, a: &u128, b: &u128) -> Result<u128> {
+        Ok(a.saturating_mul(*b))
+    }
+}
+
+impl Modular {
+    pub fn new(modulus: u64) -> Result<Self> {
+        match modulus {
+            0 => Err("modulus must be positive".into()),
+            modulus => Ok(Self(modulus)),
+        }
+    }
+
+    fn reduce(&self, value: u128) -> u64 {
+        (value % self.0 as u128) as u64
+    }
+}
+
+impl Arithmetic for Modular {
+    type Value = u64;
+
+    fn value(&self, n: u64) -> u64 {
+        n % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> Result<u64> {
+        Ok(self.reduce(*a as u128 + *b as u128))
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> Result<u64> {
+        Ok(self.reduce(*a as u128 * *b as u128))
+    }
+}
+
+impl Arithmetic for Exact {
+    type Value = BigUint;
+
+    fn value(&self, n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    fn add(&self, a: &BigUint, b: &BigUint) -> Result<BigUint> {
+        Ok(a + b)
+    }
+
+    fn mul(&self, a: &BigUint, b: &BigUint) -> Result<BigUint> {
+        Ok(a * b)
+    }
+}
+
+/// Spawn rules: a fish with timer 0 resets to `cycle - 1` and spawns a fish with timer
+/// `cycle - 1 + newborn_delay`. With a death age, fish die on reaching that age in days,
+/// counting the initial fish as newborns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifeCycle {
+    pub cycle: usize,
+    pub newborn_delay: usize,
+    pub death_age: Option<usize>,
+}
+
+impl Default for LifeCycle {
+    fn default() -> Self {
+        Self {
+            cycle: 7,
+            newborn_delay: 2,
+            death_age: None,
+        }
+    }
+}
+
+impl LifeCycle {
+    fn validate(&self) -> Result<()> {
+        if self.cycle == 0 {
+            return Err("the cycle must last at least one day".into());
+        }
+        if self.death_age == Some(0) {
+            return Err("fish must live at least one day".into());
+        }
+        match self.timers().checked_mul(self.death_age.unwrap_or(1)) {
+            Some(states) if states <= MAX_LIFE_CYCLE_STATES => Ok(()),
+            _ => {
+                let message = format!("a life cycle has at most {} states", MAX_LIFE_CYCLE_STATES);
+                Err(message.into())
+            }
+        }
+    }
+
+    /// Fails when simulating `days` days one by one takes over `MAX_UPDATES` state updates
+    fn simulate(&self, days: u64) -> Result<()> {
+        match days.checked_mul(self.states() as u64) {
+            Some(updates) if updates <= MAX_UPDATES => Ok(()),
+            _ => {
+                let message = format!(
+                    "{} days are too many to simulate the {} states of the life cycle",
+                    days,
+                    self.states()
+                );
+                Err(message.into())
+            }
+        }
+    }
+
+    fn timers(&self) -> usize {
+        self.cycle + self.newborn_delay
+    }
+
+    /// Fish are grouped by timer, and also by age when they can die
+    fn states(&self) -> usize {
+        self.timers() * self.death_age.unwrap_or(1)
+    }
+
+    /// Counts of the fish in every state one day after `counts`
+    fn day<A: Arithmetic>(&self, counts: &[A::Value], arithmetic: &A) -> Result<Vec<A::Value>> {
+        let mut next = vec![arithmetic.value(0); counts.len()];
+        for (state, count) in counts.iter().enumerate() {
+            for to in self.next(state) {
+                next[to] = arithmetic.add(&next[to], count)?;
+            }
+        }
+
+        Ok(next)
+    }
+
+    fn state(&self, timer: usize, age: usize) -> usize {
+        age * self.timers() + timer
+    }
+
+    /// States of the fish one day after a fish in `state`
+    fn next(&self, state: usize) -> Vec<usize> {
+        let (timer, age) = (state % self.timers(), state / self.timers());
+        let age = match self.death_age {
+            Some(death_age) if age + 1 == death_age => return vec![],
+            Some(_) => age + 1,
+            None => 0,
+        };
+
+        match timer {
+            0 => vec![
+                self.state(self.cycle - 1, age),
+                self.state(self.timers() - 1, 0),
+            ],
+            timer => vec![self.state(timer - 1, age)],
+        }
+    }
+
+    /// Row `from` holds the number of fish in every state one day after a fish in state `from`
+    fn transition(&self) -> Matrix<u64> {
+        let mut transition = Matrix::new(self.states(), self.states());
+        for from in 0..self.states() {
+            for to in self.next(from) {
+                transition.set(to, from, transition.get(to, from).unwrap() + 1);
+            }
+        }
+
+        transition
+    }
+}
+
+fn multiply<A: Arithmetic>(
+    arithmetic: &A,
+    a: &Matrix<A::Value>,
+    b: &Matrix<A::Value>,
+) -> Result<Matrix<A::Value>> {
+    let mut error = None;
+    let product = Matrix::from_fn(b.width, a.height, |x, y| {
+        (0..a.width)
+            .try_fold(arithmetic.value(0), |sum, k| {
+                let term = arithmetic.mul(a.get(k, y).unwrap(), b.get(x, k).unwrap())?;
+                arithmetic.add(&sum, &term)
+            })
+            .unwrap_or_else(|err| {
+                error.get_or_insert(err);
+                arithmetic.value(0)
+            })
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(product),
+    }
+}
+
+/// `matrix` to the power of `exponent` by repeated squaring
+fn power<A: Arithmetic>(
+    arithmetic: &A,
+    matrix: &Matrix<A::Value>,
+    mut exponent: u64,
+) -> Result<Matrix<A::Value>> {
+    let mut result = Matrix::from_fn(matrix.width, matrix.height, |x, y| {
+        arithmetic.value((x == y) as u64)
+    });
+    let mut square = matrix.clone();
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(arithmetic, &result, &square)?;
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            square = multiply(arithmetic, &square, &square)?;
+        }
+    }
+
+    Ok(result)
+}
 
 impl DaySolution {
-    fn solve(&self, input: Option<String>, days: usize) -> u64 {
-        let fish: Vec<i32> = input
-            .unwrap()
-            .split(',')
-            .filter_map(|s| s.parse::<i32>().ok())
-            .collect();
+    /// Number of fish in every state of the life cycle
+    fn parse(&self, input: Option<&str>, life_cycle: &LifeCycle) -> Result<Vec<u64>> {
+        life_cycle.validate()?;
+
+        let mut counts = vec![0; life_cycle.states()];
+        for timer in input.unwrap_or_default().trim().split(',') {
+            let timer = timer
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| Problem::new(1, format!("invalid timer {:?}", timer)))?;
+            if timer >= life_cycle.timers() {
+                let message = format!("timers go from 0 to {}", life_cycle.timers() - 1);
+                return Err(Problem::new(1, message).into());
+            }
+            counts[life_cycle.state(timer, 0)] += 1;
+        }
+
+        Ok(counts)
+    }
 
-        let mut counter = (0..9)
-            .map(|i| fish.iter().filter(|&&f| f == i).count() as u64)
-            .collect::<Vec<u64>>();
+    /// Population after `days` days, from the powers of the one day transition matrix.
+    /// The matrix has a row per timer and age, so death ages make it much larger: past
+    /// `MAX_STATES` states, or with fewer days than states, fish are simulated day by day.
+    pub fn population<A: Arithmetic>(
+        &self,
+        input: Option<&str>,
+        days: u64,
+        life_cycle: &LifeCycle,
+        arithmetic: &A,
+    ) -> Result<A::Value> {
+        let counts = self.parse(input, life_cycle)?.into_iter();
+        let mut counts = counts.map(|n| arithmetic.value(n)).collect::<Vec<_>>();
+        let states = counts.len() as u64;
 
-        for _ in 0..days {
-            counter.rotate_left(1);
-            counter[6] += counter[8];
+        if states <= MAX_STATES as u64 && days > states {
+            let fish = Matrix::from_fn(counts.len(), 1, |x, _| counts[x].clone());
+            let transition = life_cycle.transition().map(|&n| arithmetic.value(n));
+            let population = multiply(arithmetic, &fish, &power(arithmetic, &transition, days)?)?;
+            counts = population.data;
+        } else {
+            life_cycle.simulate(days)?;
+            for _ in 0..days {
+                counts = life_cycle.day(&counts, arithmetic)?;
+            }
         }
 
-        counter.iter().sum()
+        counts.iter().try_fold(arithmetic.value(0), |sum, count| {
+            arithmetic.add(&sum, count)
+        })
+    }
+
+    /// Population after `--days` days, or `days` by default, counted as set by `--modulus`
+    /// and `--exact`
+    fn count(&self, input: Option<&str>, days: u64) -> Result<Box<dyn Display>> {
+        let days = self.days.unwrap_or(days);
+        let life_cycle = self.life_cycle;
+
+        Ok(match (&self.modulus, self.exact) {
+            (Some(modular), _) => Box::new(self.population(input, days, &life_cycle, modular)?),
+            (None, true) => Box::new(self.population(input, days, &life_cycle, &Exact)?),
+            (None, false) => {
+                // Powers of the transition matrix can overflow well before the population
+                let population = self.population(input, days, &life_cycle, &Saturating)?;
+                Box::new(u64::try_from(population).map_err(|_| "population overflows u64")?)
+            }
+        })
+    }
+
+    /// Number of fish for every timer on each day from 0 to `days`, simulated day by day
+    pub fn series(
+        &self,
+        input: Option<&str>,
+        days: u64,
+        life_cycle: &LifeCycle,
+    ) -> Result<Vec<Vec<u64>>> {
+        life_cycle.simulate(days)?;
+        let mut counts = self.parse(input, life_cycle)?;
+        let mut series = vec![];
+
+        for day in 0..=days {
+            let mut timers = vec![0u64; life_cycle.timers()];
+            for (state, count) in counts.iter().enumerate() {
+                let timer = &mut timers[state % life_cycle.timers()];
+                *timer = Checked.add(timer, count)?;
+            }
+            series.push(timers);
+
+            if day < days {
+                counts = life_cycle.day(&counts, &Checked)?;
+            }
+        }
+
+        Ok(series)
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 80)))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        self.count(input, 80)
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, 256)))
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        self.count(input, 256)
+    }
+
+    fn configure(&mut self, options: &Options) -> Result<()> {
+        let default = LifeCycle::default();
+        self.life_cycle = LifeCycle {
+            cycle: options.cycle.unwrap_or(default.cycle),
+            newborn_delay: options.newborn_delay.unwrap_or(default.newborn_delay),
+            death_age: options.death_age,
+        };
+        self.life_cycle.validate()?;
+        self.days = options.days;
+        self.modulus = options.modulus.map(Modular::new).transpose()?;
+        self.exact = options.exact;
+        Ok(())
+    }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input), &self.life_cycle))
+    }
+
+    /// Population by timer on each of the part 2 days, or of `--days`
+    fn trace(&mut self, input: Option<&str>, format: TraceFormat) -> Result<Option<String>> {
+        let series = self.series(input, self.days.unwrap_or(256), &self.life_cycle)?;
+        let totals = series
+            .iter()
+            .map(|counts| {
+                counts
+                    .iter()
+                    .try_fold(0, |sum, count| Checked.add(&sum, count))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(match format {
+            TraceFormat::Csv => {
+                let timers = (0..series[0].len()).map(|timer| format!("timer_{}", timer));
+                let mut csv = once("day".to_string())
+                    .chain(timers)
+                    .chain(once("total".to_string()))
+                    .join(",");
+                csv.push('\n');
+
+                for (day, (counts, total)) in series.iter().zip(&totals).enumerate() {
+                    csv += &format!("{},{},{}\n", day, counts.iter().join(","), total);
+                }
+                csv
+            }
+            TraceFormat::Json => {
+                let days = series
+                    .iter()
+                    .zip(totals)
+                    .enumerate()
+                    .map(|(day, (counts, total))| {
+                        object! {
+                            day: day,
+                            timers: counts.clone(),
+                            total: total,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                json::JsonValue::Array(days).pretty(2)
+            }
+        }))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day6::DaySolution;
+    use crate::day6::{Checked, DaySolution, Exact, LifeCycle, Modular};
+    use crate::solutions::{Options, TraceFormat};
     use crate::Solution;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day6_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("5934", result.to_string())
     }
@@ -53,10 +464,202 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day6_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("26984457539", result.to_string())
     }
+
+    #[test]
+    fn population() {
+        let input = Some(include_str!("../../inputs/day6_demo.txt"));
+
+        let exact = DaySolution::default()
+            .population(input, 256, &LifeCycle::default(), &Exact)
+            .unwrap();
+        assert_eq!(exact.to_string(), "26984457539");
+
+        let exact = DaySolution::default()
+            .population(input, 5000, &LifeCycle::default(), &Exact)
+            .unwrap();
+        let modulus = 1_000_000_007;
+        let modular = DaySolution::default()
+            .population(
+                input,
+                5000,
+                &LifeCycle::default(),
+                &Modular::new(modulus).unwrap(),
+            )
+            .unwrap();
+        let remainder = exact.to_string().bytes().fold(0, |remainder, digit| {
+            (remainder * 10 + (digit - b'0') as u64) % modulus
+        });
+        assert_eq!(modular, remainder);
+
+        assert!(DaySolution::default()
+            .population(
+                input,
+                1_000_000_000_000,
+                &LifeCycle::default(),
+                &Modular::new(modulus).unwrap()
+            )
+            .is_ok());
+        assert!(DaySolution::default()
+            .population(input, 1000, &LifeCycle::default(), &Checked)
+            .is_err());
+    }
+
+    #[test]
+    fn options() {
+        let input = Some(include_str!("../../inputs/day6_demo.txt"));
+        let mut solution = DaySolution::default();
+
+        let options = Options {
+            days: Some(18),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert_eq!(solution.part_1(input).unwrap().to_string(), "26");
+
+        let options = Options {
+            days: Some(1000),
+            exact: true,
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let exact = solution.part_2(input).unwrap().to_string();
+        assert!(exact.len() > 20);
+
+        let options = Options {
+            days: Some(1000),
+            modulus: Some(1000),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let modular = solution.part_2(input).unwrap().to_string();
+        let remainder = exact[exact.len() - 3..].parse::<u64>().unwrap();
+        assert_eq!(modular, remainder.to_string());
+
+        let options = Options {
+            cycle: Some(2),
+            newborn_delay: Some(60),
+            days: Some(1111),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let population = solution.part_2(Some("61")).unwrap().to_string();
+        assert_eq!(population, "17250696346819404622");
+        assert!(DaySolution::default()
+            .population(Some("61"), 1111, &solution.life_cycle, &Checked)
+            .is_err());
+        let options = Options {
+            days: Some(1112),
+            ..options
+        };
+        solution.configure(&options).unwrap();
+        assert!(solution.part_2(Some("61")).is_err());
+
+        let options = Options {
+            modulus: Some(0),
+            ..Options::default()
+        };
+        assert!(solution.configure(&options).is_err());
+    }
+
+    #[test]
+    fn death_age() {
+        let input = Some(include_str!("../../inputs/day6_demo.txt"));
+        let mut solution = DaySolution::default();
+
+        let options = Options {
+            cycle: Some(5),
+            newborn_delay: Some(1),
+            death_age: Some(12),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let series = solution.series(input, 80, &solution.life_cycle).unwrap();
+        let total = series[80].iter().sum::<u64>();
+        assert_eq!(
+            solution.part_1(input).unwrap().to_string(),
+            total.to_string()
+        );
+
+        let options = Options {
+            death_age: Some(1000),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert_eq!(solution.part_2(input).unwrap().to_string(), "26984457539");
+
+        let options = Options {
+            days: Some(1_000_000_000_000),
+            modulus: Some(1_000_000_007),
+            death_age: Some(1000),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert!(solution.part_2(input).is_err());
+
+        let options = Options {
+            cycle: Some(0),
+            ..Options::default()
+        };
+        assert!(solution.configure(&options).is_err());
+    }
+
+    #[test]
+    fn life_cycle() {
+        let input = Some(include_str!("../../inputs/day6_demo.txt"));
+        let mortal = LifeCycle {
+            cycle: 5,
+            newborn_delay: 1,
+            death_age: Some(12),
+        };
+
+        let series = DaySolution::default().series(input, 40, &mortal).unwrap();
+        for (day, counts) in series.iter().enumerate() {
+            let population = DaySolution::default()
+                .population(input, day as u64, &mortal, &Checked)
+                .unwrap();
+            assert_eq!(counts.iter().sum::<u64>(), population);
+        }
+        assert_eq!(series[1], vec![1, 1, 2, 1, 0, 0]);
+
+        let csv = DaySolution::default()
+            .trace(input, TraceFormat::Csv)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            csv.lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "day,timer_0,timer_1,timer_2,timer_3,timer_4,timer_5,timer_6,timer_7,timer_8,total",
+                "0,0,1,1,2,1,0,0,0,0,5",
+                "1,1,1,2,1,0,0,0,0,0,5",
+            ]
+        );
+        assert_eq!(csv.lines().last().unwrap(), "256,2376852196,2731163883,2897294544,3164316379,3541830408,3681986557,4275812629,1985489551,2329711392,26984457539");
+
+        let mut solution = DaySolution::default();
+        let options = Options {
+            days: Some(18),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        let csv = solution.trace(input, TraceFormat::Csv).unwrap().unwrap();
+        assert_eq!(csv.lines().count(), 20);
+        assert!(csv.ends_with(",26\n"));
+
+        let options = Options {
+            days: Some(500),
+            ..Options::default()
+        };
+        solution.configure(&options).unwrap();
+        assert!(solution.trace(input, TraceFormat::Json).is_err());
+
+        let options = Options {
+            death_age: Some(1_000_000),
+            ..Options::default()
+        };
+        assert!(solution.configure(&options).is_err());
+    }
 }
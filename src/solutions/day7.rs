@@ -1,41 +1,57 @@
 use std::fmt::Display;
 
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
 
 impl DaySolution {
-    fn solve<F>(&self, input: Option<String>, cost: F) -> i32
+    fn parse(&self, input: Option<&str>) -> Result<Vec<i32>> {
+        let input = input.unwrap_or_default().trim();
+        if input.is_empty() {
+            return Err(Problem::input("no crab positions").into());
+        }
+
+        input
+            .split(',')
+            .map(|s| {
+                s.trim()
+                    .parse::<i32>()
+                    .map_err(|_| Problem::new(1, format!("invalid position {:?}", s)).into())
+            })
+            .collect()
+    }
+
+    fn solve<F>(&self, input: Option<&str>, cost: F) -> Result<i32>
     where
         F: Fn(i32, i32) -> i32,
     {
-        let data: Vec<i32> = input
-            .unwrap()
-            .split(',')
-            .filter_map(|s| s.parse::<i32>().ok())
-            .collect();
+        let data = self.parse(input)?;
 
         let min_align = *data.iter().min().unwrap();
         let max_align = *data.iter().max().unwrap();
 
-        (min_align..max_align)
+        Ok((min_align..=max_align)
             .map(|align| data.iter().map(|&v| cost(v, align)).sum())
             .min()
-            .unwrap()
+            .unwrap())
     }
 }
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        Ok(Box::new(self.solve(input, |v, a| (v - a).abs())))
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        Ok(Box::new(self.solve(input, |v, a| (v - a).abs())?))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
         Ok(Box::new(
-            self.solve(input, |v, a| (1..=(v - a).abs()).sum()),
+            self.solve(input, |v, a| (1..=(v - a).abs()).sum())?,
         ))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
@@ -46,9 +62,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day7_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("37", result.to_string())
     }
@@ -56,9 +70,7 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day7_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("168", result.to_string())
     }
@@ -1,8 +1,7 @@
 use std::collections::HashMap;
 use std::fmt::Display;
 
-use crate::helpers::parse_lines;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 
 #[derive(Default)]
 pub struct DaySolution;
@@ -20,31 +19,66 @@ impl Digit for str {
     }
 }
 
+/// Ten unique signal patterns and the four output digits of a display, as segment bits
+struct Entry {
+    patterns: Vec<u8>,
+    output: Vec<u8>,
+}
+
+impl DaySolution {
+    fn parse_entry(line: &str) -> core::result::Result<Entry, String> {
+        let (patterns, output) = line
+            .split_once(" | ")
+            .ok_or("missing the ` | ` separator")?;
+        let (patterns, output) = (
+            patterns.split_whitespace().collect::<Vec<_>>(),
+            output.split_whitespace().collect::<Vec<_>>(),
+        );
+
+        if patterns.len() != 10 {
+            return Err(format!("expected 10 patterns, found {}", patterns.len()));
+        }
+        if output.len() != 4 {
+            return Err(format!("expected 4 output values, found {}", output.len()));
+        }
+        let invalid = patterns.iter().chain(output.iter()).find(|digit| {
+            !digit.chars().all(|ch| ('a'..='g').contains(&ch))
+                || digit.to_bin().count_ones() as usize != digit.len()
+        });
+        if let Some(digit) = invalid {
+            return Err(format!("invalid segments {:?}", digit));
+        }
+
+        Ok(Entry {
+            patterns: patterns.iter().map(|digit| digit.to_bin()).collect(),
+            output: output.iter().map(|digit| digit.to_bin()).collect(),
+        })
+    }
+
+    fn parse(&self, input: Option<&str>) -> Result<Vec<Entry>> {
+        solutions::parse_lines(input.unwrap_or_default(), Self::parse_entry)
+    }
+}
+
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let result = parse_lines::<String>(input)
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let result = self
+            .parse(input)?
             .iter()
-            .flat_map(|s| s.split_once(" | ").unwrap().1.split_ascii_whitespace())
-            .filter(|s| matches!(s.len(), 2 | 3 | 4 | 7))
+            .flat_map(|entry| entry.output.iter())
+            .filter(|digit| matches!(digit.count_ones(), 2 | 3 | 4 | 7))
             .count();
 
         Ok(Box::new(result))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let entries = parse_lines::<String>(input)
-            .iter()
-            .map(|s| {
-                s.split(" | ")
-                    .map(|s| s.split_ascii_whitespace().map(Digit::to_bin).collect())
-                    .collect()
-            })
-            .collect::<Vec<Vec<Vec<_>>>>();
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let entries = self.parse(input)?;
 
         let result = entries
             .iter()
             .map(|entry| {
-                let (patterns, output) = (&entry[0], &entry[1]);
+                let (patterns, output) = (&entry.patterns, &entry.output);
 
                 let digit_one = patterns.iter().find(|a| a.count_ones() == 2).unwrap();
                 let digit_four = patterns.iter().find(|a| a.count_ones() == 4).unwrap();
@@ -76,6 +110,10 @@ impl Solution for DaySolution {
 
         Ok(Box::new(result))
     }
+
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
+    }
 }
 
 #[cfg(test)]
@@ -86,9 +124,7 @@ mod tests {
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day8_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("26", result.to_string())
     }
@@ -96,10 +132,30 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day8_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("61229", result.to_string())
     }
+
+    #[test]
+    fn lint() {
+        let input = include_str!("../../inputs/day8_demo.txt");
+        assert_eq!(DaySolution.lint(input), vec![]);
+
+        let input = "ab cd | ab cd ef gh\nab | abz\nab cd ef gh ij ab cd ef gh ij ab cd ef gh ij\na b c d e f g ab ac ad | ab cd ef gh";
+        let problems = DaySolution
+            .lint(input)
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            problems,
+            vec![
+                "line 1: expected 10 patterns, found 2",
+                "line 2: expected 10 patterns, found 1",
+                "line 3: missing the ` | ` separator",
+                "line 4: invalid segments \"gh\"",
+            ]
+        );
+    }
 }
@@ -1,89 +1,99 @@
-use crate::helpers::*;
+use crate::matrix::image::{palette, Rgb, BLACK};
 use crate::matrix::Matrix;
-use crate::solutions::{Result, Solution};
+use crate::solutions::{self, Problem, Result, Solution};
 use std::collections::VecDeque;
 use std::fmt::Display;
 
 #[derive(Default)]
 pub struct DaySolution;
 
-impl DaySolution {}
+impl DaySolution {
+    fn parse(&self, input: Option<&str>) -> Result<Matrix<u8>> {
+        Ok(Matrix::parse(input.unwrap_or_default())?)
+    }
+
+    /// Labels every cell with the index of its basin, heights of 9 belong to no basin
+    fn basins(&self, heightmap: &Matrix<u8>) -> Matrix<Option<usize>> {
+        let mut basins = Matrix::from_fn(heightmap.width, heightmap.height, |_, _| None);
+        let mut count = 0;
+
+        for (&height, (x, y)) in heightmap.iter() {
+            if height >= 9 || basins.get(x, y) != Some(&None) {
+                continue;
+            }
+
+            let mut queue = VecDeque::from([(x as i32, y as i32)]);
+            basins.set(x, y, Some(count));
+            while let Some((x, y)) = queue.pop_front() {
+                for (&height, (x, y)) in heightmap.neighbours4_iter(x, y) {
+                    if height < 9 && basins.get(x, y) == Some(&None) {
+                        basins.set(x, y, Some(count));
+                        queue.push_back((x, y));
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        basins
+    }
+}
 
 impl Solution for DaySolution {
-    fn part_1(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let result: usize = Matrix::<u8>::from(&input.unwrap())
-            .unwrap()
+    fn part_1(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let result: usize = self
+            .parse(input)?
             .iter_with_self()
-            .filter_map(|(value, (x, y), matrix)| {
-                matrix
-                    .neighbours4(x, y)
-                    .iter()
-                    .all(|&v| v > value)
-                    .then(|| (value + 1) as usize)
-            })
+            .filter(|&(value, (x, y), matrix)| matrix.neighbours4(x, y).iter().all(|&v| v > value))
+            .map(|(value, _, _)| (value + 1) as usize)
             .sum();
 
         Ok(Box::new(result))
     }
 
-    fn part_2(&mut self, input: Option<String>) -> Result<Box<dyn Display>> {
-        let lines = parse_lines::<String>(input);
-        let width = lines[0].len();
-
-        let mut heightmap = lines
-            .iter()
-            .flat_map(|line| line.chars().map(|ch| ch.to_digit(10).unwrap()))
-            .collect::<Vec<_>>();
-
-        let mut counts: Vec<i32> = vec![];
-        let mut queue: VecDeque<_> = VecDeque::new();
-
-        for i in 0..heightmap.len() {
-            if heightmap[i] >= 9 {
-                continue;
-            }
+    fn part_2(&mut self, input: Option<&str>) -> Result<Box<dyn Display>> {
+        let heightmap = self.parse(input)?;
 
-            queue.push_back(i);
-
-            let mut count = 0;
-            while let Some(i) = queue.pop_front() {
-                if heightmap[i] == 9 {
-                    continue;
+        let mut sizes: Vec<usize> = vec![];
+        for (&basin, _) in self.basins(&heightmap).iter() {
+            if let Some(basin) = basin {
+                if basin >= sizes.len() {
+                    sizes.resize(basin + 1, 0);
                 }
+                sizes[basin] += 1;
+            }
+        }
 
-                heightmap[i] = 9;
-                count += 1;
+        sizes.sort_unstable();
 
-                queue.extend(
-                    heightmap
-                        .neighbour_indexes(i, width)
-                        .into_iter()
-                        .filter(|&i| heightmap[i] < 9),
-                );
-            }
+        Ok(Box::new(sizes.iter().rev().take(3).product::<usize>()))
+    }
 
-            if count > 0 {
-                counts.push(count);
-            }
-        }
+    fn render(&mut self, input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        let heightmap = self.parse(input)?;
+        let basins = self.basins(&heightmap);
 
-        counts.sort_unstable();
+        Ok(Some(
+            basins.map(|basin| basin.map(palette).unwrap_or(BLACK)),
+        ))
+    }
 
-        Ok(Box::new(counts.iter().rev().take(3).product::<i32>()))
+    fn lint(&mut self, input: &str) -> Vec<Problem> {
+        solutions::lint(self.parse(Some(input)))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::day9::DaySolution;
+    use crate::matrix::image::BLACK;
     use crate::Solution;
+    use std::collections::HashSet;
 
     #[test]
     fn part_1() {
         let input = include_str!("../../inputs/day9_demo.txt");
-        let result = DaySolution::default()
-            .part_1(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_1(Some(input)).unwrap();
 
         assert_eq!("15", result.to_string())
     }
@@ -91,10 +101,23 @@ mod tests {
     #[test]
     fn part_2() {
         let input = include_str!("../../inputs/day9_demo.txt");
-        let result = DaySolution::default()
-            .part_2(Some(input.to_string()))
-            .unwrap();
+        let result = DaySolution::default().part_2(Some(input)).unwrap();
 
         assert_eq!("1134", result.to_string())
     }
+
+    #[test]
+    fn render() {
+        let input = include_str!("../../inputs/day9_demo.txt");
+        let image = DaySolution.render(Some(input)).unwrap().unwrap();
+
+        let colours = image
+            .iter()
+            .map(|(&colour, _)| colour)
+            .collect::<HashSet<_>>();
+        assert_eq!(colours.len(), 5);
+        assert_eq!(image.get(2, 0), Some(&BLACK));
+        assert_eq!(image.get(0, 0), image.get(0, 1));
+        assert_ne!(image.get(0, 0), image.get(9, 0));
+    }
 }
@@ -1,4 +1,11 @@
+use std::error::Error;
 use std::fmt::Display;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::matrix::image::Rgb;
+use crate::matrix::parse::MatrixError;
+use crate::matrix::Matrix;
 
 pub mod day1;
 pub mod day10;
@@ -26,9 +33,238 @@ pub mod day7;
 pub mod day8;
 pub mod day9;
 
-pub type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+pub type Result<T> = core::result::Result<T, Box<dyn Error>>;
+
+/// Problem found by a parser, `line` is 1-based or 0 when it concerns the whole input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new<S: Into<String>>(line: usize, message: S) -> Problem {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Problem for the input as a whole
+    pub fn input<S: Into<String>>(message: S) -> Problem {
+        Self::new(0, message)
+    }
+
+    /// Same problem for a part of the input starting on line `start`
+    pub fn within(self, start: usize) -> Problem {
+        match self.line {
+            0 => self,
+            line => Problem::new(start + line - 1, self.message),
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            0 => write!(f, "input: {}", self.message),
+            line => write!(f, "line {}: {}", line, self.message),
+        }
+    }
+}
+
+impl Error for Problem {}
+
+/// Every problem found by a parser that doesn't stop at the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problems(pub Vec<Problem>);
+
+impl Display for Problems {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let problems = self.0.iter().map(|problem| problem.to_string());
+        write!(f, "{}", problems.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+impl Error for Problems {}
+
+impl Problems {
+    /// `value` when no problem was found, otherwise the problems in line order
+    pub fn result<T>(mut problems: Vec<Problem>, value: T) -> Result<T> {
+        problems.sort_by_key(|problem| problem.line);
+        match problems.is_empty() {
+            true => Ok(value),
+            false => Err(Problems(problems).into()),
+        }
+    }
+}
+
+/// Problems behind a parser error, errors without a line concern the whole input
+pub fn problems(error: Box<dyn Error>) -> Vec<Problem> {
+    let error = match error.downcast::<Problems>() {
+        Ok(problems) => return problems.0,
+        Err(error) => error,
+    };
+    let error = match error.downcast::<Problem>() {
+        Ok(problem) => return vec![*problem],
+        Err(error) => error,
+    };
+
+    match error.downcast::<MatrixError>() {
+        Ok(error) => match *error {
+            MatrixError::Empty => vec![Problem::input(error.to_string())],
+            MatrixError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => vec![Problem::new(
+                line,
+                format!("expected {} cells, found {}", expected, found),
+            )],
+            MatrixError::InvalidCell {
+                line,
+                column,
+                value,
+            } => vec![Problem::new(
+                line,
+                format!("column {}: invalid cell {:?}", column, value),
+            )],
+        },
+        Err(error) => vec![Problem::input(error.to_string())],
+    }
+}
+
+/// Lint result of a parser, no problem when it succeeds
+pub fn lint<T>(parsed: Result<T>) -> Vec<Problem> {
+    parsed.err().map(problems).unwrap_or_default()
+}
+
+/// Parses every non-empty line of `lines`, numbered lines of the input, with `func` and adds
+/// a problem for every line that fails
+pub fn parse_each<'a, T, F>(
+    lines: impl IntoIterator<Item = (usize, &'a str)>,
+    problems: &mut Vec<Problem>,
+    mut func: F,
+) -> Vec<T>
+where
+    F: FnMut(&'a str) -> core::result::Result<T, String>,
+{
+    let mut values = vec![];
+    for (line, text) in lines {
+        if text.trim().is_empty() {
+            continue;
+        }
+        match func(text) {
+            Ok(value) => values.push(value),
+            Err(message) => problems.push(Problem::new(line, message)),
+        }
+    }
+
+    values
+}
+
+/// Parses every non-empty line with `func`, stopping only once all of them have been tried
+pub fn parse_lines<'a, T, F>(input: &'a str, func: F) -> Result<Vec<T>>
+where
+    F: FnMut(&'a str) -> core::result::Result<T, String>,
+{
+    let mut problems = vec![];
+    let values = parse_each((1..).zip(input.lines()), &mut problems, func);
+
+    Problems::result(problems, values)
+}
+
+/// Format of the per-step trace written by `Solution::trace`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+impl TraceFormat {
+    pub fn from_path(path: &Path) -> Option<TraceFormat> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(TraceFormat::Csv),
+            "json" => Some(TraceFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Settings from the command line, every day reads the ones it supports
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// Step the input grid was saved at when it comes from a snapshot
+    pub start_step: usize,
+    /// Measurements summed in each window of the analysis
+    pub window: Option<usize>,
+    /// Largest jumps listed by the analysis
+    pub jumps: Option<usize>,
+    /// Games played with shuffled draws by the analysis
+    pub trials: Option<usize>,
+    /// Seed of the shuffled draws
+    pub seed: Option<u64>,
+    /// Bits kept on ties by the gamma rate, the oxygen and the CO2 ratings
+    pub ties: Option<String>,
+    /// Segments that have to overlap at a point
+    pub threshold: Option<usize>,
+    /// Days between two spawns of a fish
+    pub cycle: Option<usize>,
+    /// Days a newborn fish waits on top of the cycle before its first spawn
+    pub newborn_delay: Option<usize>,
+    /// Age in days at which fish die, they live forever without one
+    pub death_age: Option<usize>,
+    /// Days simulated instead of the ones the puzzle asks for
+    pub days: Option<u64>,
+    /// Count modulo this number so that any number of days fits
+    pub modulus: Option<u64>,
+    /// Count with arbitrary precision instead of failing when `u64` overflows
+    pub exact: bool,
+    /// Position a planned course has to reach, horizontal then depth
+    pub target: Option<(i64, i64)>,
+    /// Largest value of a single planned command
+    pub max_value: Option<i64>,
+    /// Plan for the part 1 movement model instead of the part 2 one
+    pub simple: bool,
+}
 
 pub trait Solution {
-    fn part_1(&mut self, _input: Option<String>) -> Result<Box<dyn Display>>;
-    fn part_2(&mut self, _input: Option<String>) -> Result<Box<dyn Display>>;
+    fn part_1(&mut self, _input: Option<&str>) -> Result<Box<dyn Display>>;
+    fn part_2(&mut self, _input: Option<&str>) -> Result<Box<dyn Display>>;
+
+    /// Applies the command line settings, called before anything else is run
+    fn configure(&mut self, _options: &Options) -> Result<()> {
+        Ok(())
+    }
+
+    /// Final grid of the day rendered as colours, used for image export
+    fn render(&mut self, _input: Option<&str>) -> Result<Option<Matrix<Rgb>>> {
+        Ok(None)
+    }
+
+    /// Grid of the day after `steps` steps, written with the same characters as the input
+    /// so that it can be fed back as an input
+    fn snapshot(&mut self, _input: Option<&str>, _steps: usize) -> Result<Option<Matrix<char>>> {
+        Ok(None)
+    }
+
+    /// Per-step record of the day's simulation, `None` when the day has nothing to trace
+    fn trace(&mut self, _input: Option<&str>, _format: TraceFormat) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Detailed report on the input streamed from `reader`, `None` when the day has no analysis
+    fn analyze(&mut self, _reader: &mut dyn BufRead) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Input planned to reach the target of the command line, `None` when the day can't plan
+    fn plan(&mut self) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Runs the day's parser on the input without solving, returning every problem it found
+    fn lint(&mut self, input: &str) -> Vec<Problem>;
 }